@@ -10,20 +10,62 @@
 //! cargo run -p x402-miden-server-example
 //! ```
 //!
+//! Prices default to 1 USDC on Miden testnet. Set `PRICING_CONFIG` to a JSON
+//! file (see `pricing.example.json`) to configure the recipient and price:
+//!
+//! ```bash
+//! PRICING_CONFIG=examples/server-example/pricing.example.json \
+//!     cargo run -p x402-miden-server-example
+//! ```
+//!
 //! # Endpoints
 //!
 //! - `GET /`              - Free endpoint
 //! - `GET /paid-content`  - Returns 402 with Miden payment requirements
 //! - `GET /price-info`    - Shows the price tag configuration
 
+use std::sync::Arc;
+
 use base64::{Engine, engine::general_purpose};
 
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
 use x402_chain_miden::V2MidenExact;
-use x402_chain_miden::chain::MidenTokenDeployment;
+use x402_chain_miden::chain::{
+    MidenAccountAddress, MidenDeployedTokenAmount, MidenTokenDeployment,
+};
+
+/// Who gets paid, and how much, for the paid endpoint.
+///
+/// The price uses the [`MidenDeployedTokenAmount`] wire shape: give either
+/// `amount` in base units (`"1000000"`) or `amountDecimal` in whole tokens
+/// (`"1.0"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PricingConfig {
+    /// The account receiving payments.
+    pay_to: MidenAccountAddress,
+    /// Price of `/paid-content`.
+    paid_content: MidenDeployedTokenAmount,
+}
+
+impl PricingConfig {
+    /// Loads the file named by `PRICING_CONFIG`, or falls back to 1 USDC on
+    /// testnet.
+    fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        match std::env::var("PRICING_CONFIG") {
+            Ok(path) => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+            Err(_) => Ok(Self {
+                pay_to: "0xaabbccddeeff00102233aabbccddee".parse()?,
+                paid_content: MidenTokenDeployment::testnet_usdc().amount(1_000_000), // 1 USDC
+            }),
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -34,10 +76,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .init();
 
+    let pricing = PricingConfig::load()?;
+    tracing::info!(
+        "Charging {} to {} for /paid-content",
+        pricing.paid_content,
+        pricing.pay_to
+    );
+
     let app = Router::new()
         .route("/", get(root_handler))
         .route("/paid-content", get(paid_content_handler))
-        .route("/price-info", get(price_info_handler));
+        .route("/price-info", get(price_info_handler))
+        .with_state(Arc::new(pricing));
 
     let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
     let bind = format!("0.0.0.0:{port}");
@@ -60,14 +110,9 @@ async fn root_handler() -> impl IntoResponse {
 ///
 /// In production, the `x402-axum` middleware would handle this automatically.
 /// This example manually constructs the 402 response to show the structure.
-async fn paid_content_handler() -> impl IntoResponse {
-    // Create the price tag for 1 USDC on Miden testnet
-    let recipient = "0xaabbccddeeff00112233aabbccddee"
-        .parse()
-        .expect("valid recipient address");
-
-    let usdc = MidenTokenDeployment::testnet_usdc();
-    let price_tag = V2MidenExact::price_tag(recipient, usdc.amount(1_000_000)); // 1 USDC
+async fn paid_content_handler(State(pricing): State<Arc<PricingConfig>>) -> impl IntoResponse {
+    // Create the price tag from the pricing configuration
+    let price_tag = V2MidenExact::price_tag(pricing.pay_to.clone(), pricing.paid_content.clone());
 
     // Build the PaymentRequired response
     // In production, x402-axum middleware does this automatically
@@ -100,13 +145,8 @@ async fn paid_content_handler() -> impl IntoResponse {
 }
 
 /// Shows the price tag configuration without requiring payment.
-async fn price_info_handler() -> impl IntoResponse {
-    let recipient = "0xaabbccddeeff00112233aabbccddee"
-        .parse()
-        .expect("valid recipient address");
-
-    let usdc = MidenTokenDeployment::testnet_usdc();
-    let price_tag = V2MidenExact::price_tag(recipient, usdc.amount(1_000_000));
+async fn price_info_handler(State(pricing): State<Arc<PricingConfig>>) -> impl IntoResponse {
+    let price_tag = V2MidenExact::price_tag(pricing.pay_to.clone(), pricing.paid_content.clone());
 
     Json(serde_json::json!({
         "scheme": "exact",
@@ -115,6 +155,9 @@ async fn price_info_handler() -> impl IntoResponse {
         "asset": price_tag.requirements.asset.to_string(),
         "payTo": price_tag.requirements.pay_to.to_string(),
         "maxTimeoutSeconds": price_tag.requirements.max_timeout_seconds,
-        "description": "1 USDC on Miden testnet",
+        "description": format!(
+            "{} on Miden {}",
+            pricing.paid_content, pricing.paid_content.token.chain_reference
+        ),
     }))
 }
@@ -21,7 +21,12 @@
 //! - `HOST`            - Bind address (default: 0.0.0.0)
 //! - `MIDEN_RPC_URL`   - Miden node RPC URL (default: https://rpc.testnet.miden.io)
 //! - `MIDEN_NETWORK`   - Network: "testnet" or "mainnet" (default: testnet)
+//! - `FAUCET_ID`       - Payment token faucet (default: testnet faucet; on mainnet
+//!   falls back to `MIDEN_MAINNET_FAUCET_ID` and must be set)
+//! - `MAX_HEADER_FIELD_BYTES` - Max decoded size of a hex payment header field (default: 16384)
+//! - `NOTE_TAG_CHECK`  - "enforce" or "warn" on note tag mismatch (default: enforce)
 
+use axum::body::Bytes;
 use axum::error_handling::HandleErrorLayer;
 use axum::extract::{DefaultBodyLimit, State};
 use axum::http::StatusCode;
@@ -38,15 +43,27 @@ use tower::buffer::BufferLayer;
 use tower::limit::RateLimitLayer;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
-use x402_chain_miden::chain::{MidenChainConfig, MidenChainProvider, MidenChainReference};
+use x402_chain_miden::chain::{
+    MidenAccountAddress, MidenChainConfig, MidenChainProvider, MidenChainReference,
+    MidenTokenDeployment, RpcUrl,
+};
+use x402_chain_miden::is_mainnet_placeholder_faucet;
 use x402_chain_miden::lightweight::{
-    FacilitatorChainState, PaymentContext,
+    DEFAULT_MAX_HEADER_FIELD_BYTES, FacilitatorChainState, PayloadLimits, PaymentContext,
+    describe_payload_error,
     server::{DEFAULT_CONTEXT_TIMEOUT_SECS, create_payment_requirement},
     types::LightweightPaymentHeader,
     verify_lightweight_payment_full,
 };
+use x402_chain_miden::v2_miden_exact::MidenExactError;
 use x402_types::chain::{ChainId, ChainProviderOps};
 
+/// How long a payment context is kept, in seconds.
+///
+/// Expired contexts stay for one more timeout, so that a late payment is told
+/// its context expired rather than that it is unknown.
+const CONTEXT_RETENTION_SECS: u64 = 2 * DEFAULT_CONTEXT_TIMEOUT_SECS;
+
 /// Simple atomic counters for Prometheus metrics.
 struct Metrics {
     lightweight_verify_requests_total: AtomicU64,
@@ -101,21 +118,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     // Read configuration from environment
-    let rpc_url =
-        env::var("MIDEN_RPC_URL").unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string());
+    let rpc_url: RpcUrl = env::var("MIDEN_RPC_URL")
+        .unwrap_or_else(|_| "https://rpc.testnet.miden.io".to_string())
+        .parse()?;
     let network = env::var("MIDEN_NETWORK").unwrap_or_else(|_| "testnet".to_string());
-    let faucet_id =
-        env::var("FAUCET_ID").unwrap_or_else(|_| "0x37d5977a8e16d8205a360820f0230f".to_string());
 
     // Build Miden provider
     let chain_reference = MidenChainReference::try_from(network.as_str())
         .expect("Invalid MIDEN_NETWORK: must be 'testnet' or 'mainnet'");
 
-    let config = MidenChainConfig {
-        chain_reference,
-        rpc_url,
+    // Mainnet has no published faucet yet: require an explicit one and
+    // refuse to start with the placeholder.
+    let is_mainnet = chain_reference == MidenChainReference::mainnet();
+    let faucet_id = match env::var("FAUCET_ID") {
+        Ok(faucet_id) => faucet_id,
+        Err(_) if is_mainnet => MidenTokenDeployment::mainnet_usdc()?.faucet_id.to_hex(),
+        Err(_) => "0x37d5977a8e16d8205a360820f0230f".to_string(),
     };
-    let provider = MidenChainProvider::from_config(&config);
+    if is_mainnet {
+        let faucet: MidenAccountAddress = faucet_id.parse()?;
+        if is_mainnet_placeholder_faucet(&faucet) {
+            return Err("FAUCET_ID is the mainnet placeholder faucet; refusing to start".into());
+        }
+    }
+
+    let config = MidenChainConfig::new(chain_reference, rpc_url);
+    let provider = MidenChainProvider::try_from_config(&config)?;
 
     tracing::info!(
         chain_id = %provider.chain_id(),
@@ -126,8 +154,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let chain_id = provider.chain_id();
 
     // Build chain state for lightweight verification (block header cache)
+    let max_header_field_bytes = env::var("MAX_HEADER_FIELD_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_HEADER_FIELD_BYTES);
+    let enforce_note_tag = env::var("NOTE_TAG_CHECK").map_or(true, |v| v != "warn");
     let chain_state =
-        FacilitatorChainState::new(config.rpc_url.clone(), config.chain_reference.clone());
+        FacilitatorChainState::new(config.rpc_url.to_string(), config.chain_reference.clone())
+            .with_max_header_field_bytes(max_header_field_bytes)
+            .with_note_tag_enforcement(enforce_note_tag);
 
     // Start background sync for block header caching
     let chain_state_bg = chain_state.clone();
@@ -303,9 +338,6 @@ struct PaymentRequirementRequest {
     asset: String,
     /// The required payment amount in the token's smallest unit.
     amount: u64,
-    /// The note tag for efficient filtering (optional, defaults to 0).
-    #[serde(default)]
-    note_tag: u32,
 }
 
 /// Response body for `POST /payment-requirement`.
@@ -322,18 +354,22 @@ struct PaymentRequirementResponse {
 /// Generates a lightweight payment requirement and stores the context.
 async fn payment_requirement_handler(
     State(state): State<Arc<AppState>>,
-    Json(body): Json<PaymentRequirementRequest>,
+    body: Bytes,
 ) -> impl IntoResponse {
     state
         .metrics
         .payment_requirement_requests_total
         .fetch_add(1, Ordering::Relaxed);
 
+    let body: PaymentRequirementRequest = match parse_json_body(&body) {
+        Ok(body) => body,
+        Err(response) => return response,
+    };
+
     let (requirement, context) = match create_payment_requirement(
         &body.recipient,
         &body.asset,
         body.amount,
-        body.note_tag,
         state.chain_id.clone(),
     ) {
         Ok(result) => result,
@@ -365,7 +401,7 @@ async fn payment_requirement_handler(
     match state.payment_contexts.write() {
         Ok(mut contexts) => {
             // Prune expired contexts while we have the write lock
-            contexts.retain(|_, ctx| !ctx.is_expired(DEFAULT_CONTEXT_TIMEOUT_SECS));
+            contexts.retain(|_, ctx| !ctx.is_expired(CONTEXT_RETENTION_SECS));
             contexts.insert(context_id.clone(), context);
 
             tracing::info!(
@@ -408,36 +444,92 @@ async fn payment_requirement_handler(
 #[serde(rename_all = "camelCase")]
 struct VerifyLightweightRequest {
     /// The payment context ID returned by `/payment-requirement`.
+    #[serde(alias = "payment_context_id")]
     payment_context_id: String,
     /// The lightweight payment header from the agent.
+    #[serde(alias = "payment_header")]
     payment_header: LightweightPaymentHeader,
 }
 
+/// Parses a JSON request body, accepting camelCase and snake_case field
+/// names, and turns failures into a 400 naming the problem.
+///
+/// Used instead of the `Json` extractor, whose rejection text does not say
+/// which spellings a missing field may use.
+fn parse_json_body<T: serde::de::DeserializeOwned>(
+    body: &[u8],
+) -> Result<T, (StatusCode, Json<serde_json::Value>)> {
+    serde_json::from_slice(body).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "invalid_request",
+                "message": describe_payload_error(&e),
+            })),
+        )
+    })
+}
+
 /// Verifies a lightweight payment header against a stored payment context.
 async fn verify_lightweight_handler(
     State(state): State<Arc<AppState>>,
-    Json(body): Json<VerifyLightweightRequest>,
+    body: Bytes,
 ) -> impl IntoResponse {
     state
         .metrics
         .lightweight_verify_requests_total
         .fetch_add(1, Ordering::Relaxed);
 
-    // 1. Prune expired contexts, then look up the requested one.
+    let body: VerifyLightweightRequest = match parse_json_body(&body) {
+        Ok(body) => body,
+        Err(response) => {
+            state
+                .metrics
+                .lightweight_verify_errors_total
+                .fetch_add(1, Ordering::Relaxed);
+            return response;
+        }
+    };
+
+    // 1. Reject structurally invalid headers as bad requests before touching
+    //    any state; verification failures proper are reported as 422.
+    let limits = PayloadLimits {
+        max_field_bytes: state.chain_state.max_header_field_bytes(),
+    };
+    if let Err(e) = body.payment_header.validate(&limits) {
+        state
+            .metrics
+            .lightweight_verify_errors_total
+            .fetch_add(1, Ordering::Relaxed);
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "invalid_payload",
+                "message": e.to_string(),
+                "field": e.field(),
+                "reason": e.code(),
+            })),
+        );
+    }
+
+    // 2. Prune contexts past their retention, then look up the requested one.
     //    We take a write lock so we can remove stale entries before lookup.
     let context = match state.payment_contexts.write() {
         Ok(mut contexts) => {
-            contexts.retain(|_, ctx| !ctx.is_expired(DEFAULT_CONTEXT_TIMEOUT_SECS));
+            contexts.retain(|_, ctx| !ctx.is_expired(CONTEXT_RETENTION_SECS));
             match contexts.get(&body.payment_context_id) {
                 Some(ctx) => {
-                    // Clone the relevant data we need for verification
-                    PaymentContext::new(
+                    // Clone the relevant data we need for verification,
+                    // keeping the creation time that expiry is measured from.
+                    let mut context = PaymentContext::new(
                         ctx.recipient_digest.clone(),
                         ctx.asset_faucet_id.clone(),
                         ctx.amount,
                         ctx.note_tag,
                         ctx.serial_num.clone(),
-                    )
+                    );
+                    context.created_at = ctx.created_at;
+                    context
                 }
                 None => {
                     state
@@ -473,22 +565,24 @@ async fn verify_lightweight_handler(
         }
     };
 
-    // 2. Check expiry before performing full verification
+    // 3. Check expiry before performing full verification, reporting it the
+    //    same way verification does so the agent knows to pay afresh.
     if context.is_expired(DEFAULT_CONTEXT_TIMEOUT_SECS) {
         state
             .metrics
             .lightweight_verify_errors_total
             .fetch_add(1, Ordering::Relaxed);
-        return (
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(serde_json::json!({
-                "error": "context_expired",
-                "message": "Payment context has expired",
-            })),
-        );
+        let current = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system clock is before Unix epoch")
+            .as_secs();
+        return verify_error_response(&MidenExactError::TransactionExpired {
+            expired_at: context.expires_at(DEFAULT_CONTEXT_TIMEOUT_SECS),
+            current,
+        });
     }
 
-    // 3. Verify the lightweight payment using full crypto verification
+    // 4. Verify the lightweight payment using full crypto verification
     //    (NoteId reconstruction + SparseMerklePath + FacilitatorChainState)
     let result =
         verify_lightweight_payment_full(&context, &body.payment_header, &state.chain_state).await;
@@ -502,6 +596,10 @@ async fn verify_lightweight_handler(
                     tracing::info!(
                         context_id = %body.payment_context_id,
                         note_id = %response.note_id,
+                        note_commitment = response
+                            .details
+                            .as_ref()
+                            .map_or("", |details| details.note_commitment.as_str()),
                         block_num = response.block_num,
                         "Lightweight payment verified and context consumed"
                     );
@@ -526,13 +624,125 @@ async fn verify_lightweight_handler(
                 context_id = %body.payment_context_id,
                 "Lightweight verify failed"
             );
-            (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(serde_json::json!({
-                    "error": "lightweight_verification_failed",
-                    "message": e.to_string(),
-                })),
-            )
+            verify_error_response(&e)
         }
     }
 }
+
+/// Maps a failed lightweight verification to its HTTP response.
+///
+/// Invalid payments are the client's problem (422); RPC failures are ours or
+/// the node's (502).
+fn verify_error_response(e: &MidenExactError) -> (StatusCode, Json<serde_json::Value>) {
+    let (status, error) = if matches!(e, MidenExactError::TransactionExpired { .. }) {
+        (StatusCode::UNPROCESSABLE_ENTITY, "payment_expired")
+    } else if e.is_verification_error() {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "lightweight_verification_failed",
+        )
+    } else {
+        (StatusCode::BAD_GATEWAY, "provider_error")
+    };
+    let mut body = serde_json::json!({
+        "error": error,
+        "message": e.to_string(),
+    });
+    if let Some((field, reason)) = e.field_error() {
+        body["field"] = field.into();
+        body["reason"] = reason.into();
+    }
+    (status, Json(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_context(context_id: &str, created_at: u64) -> Arc<AppState> {
+        let mut context = PaymentContext::new(
+            "0xdigest".to_string(),
+            "0x37d5977a8e16d8205a360820f0230f".to_string(),
+            1_000_000,
+            0,
+            None,
+        );
+        context.created_at = created_at;
+        Arc::new(AppState {
+            faucet_id: context.asset_faucet_id.clone(),
+            metrics: Metrics::new(),
+            payment_contexts: RwLock::new(HashMap::from([(context_id.to_string(), context)])),
+            chain_state: FacilitatorChainState::new(
+                "http://127.0.0.1:1".to_string(),
+                MidenChainReference::testnet(),
+            ),
+            chain_id: ChainId::new("miden", "testnet"),
+        })
+    }
+
+    async fn verify(state: Arc<AppState>, context_id: &str) -> (StatusCode, serde_json::Value) {
+        let header = LightweightPaymentHeader {
+            payload_version: 1,
+            note_id: format!("0x{}", "ab".repeat(32)),
+            block_num: 10,
+            note_index: 0,
+            note_metadata: "0xaabb".to_string(),
+            inclusion_proof: "0xcafe".to_string(),
+        };
+        let body = serde_json::json!({
+            "paymentContextId": context_id,
+            "paymentHeader": header,
+        });
+        let response = verify_lightweight_handler(State(state), Bytes::from(body.to_string()))
+            .await
+            .into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_verify_expired_context_reports_payment_expired() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let created_at = now - DEFAULT_CONTEXT_TIMEOUT_SECS - 10;
+        let state = state_with_context("ctx-late", created_at);
+
+        let (status, body) = verify(state.clone(), "ctx-late").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "payment_expired");
+        let expired_at = created_at + DEFAULT_CONTEXT_TIMEOUT_SECS;
+        let message = body["message"].as_str().unwrap();
+        assert!(
+            message.starts_with(&format!(
+                "Payment expired at {expired_at}, current time is "
+            )),
+            "{message}"
+        );
+        assert_eq!(
+            state
+                .metrics
+                .lightweight_verify_errors_total
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_context_past_retention_is_not_found() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let state = state_with_context("ctx-gone", now - CONTEXT_RETENTION_SECS);
+
+        let (status, body) = verify(state.clone(), "ctx-gone").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "context_not_found");
+        assert!(state.payment_contexts.read().unwrap().is_empty());
+    }
+}
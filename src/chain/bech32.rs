@@ -0,0 +1,184 @@
+//! Minimal bech32m (BIP-350) encoding and decoding.
+//!
+//! Only what [`MidenAccountAddress`](super::MidenAccountAddress) needs to
+//! read and write Miden's bech32 account addresses: checksum validation,
+//! case handling and 5-bit/8-bit regrouping.
+
+/// The bech32 data alphabet.
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The bech32m checksum constant.
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Maximum length of a bech32 string.
+const MAX_LENGTH: usize = 90;
+
+/// Why a string is not valid bech32m.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Bech32Error {
+    /// The string mixes upper and lower case.
+    #[error("mixed case")]
+    MixedCase,
+    /// The string has no `1` separator, or an empty human-readable part.
+    #[error("missing separator or human-readable part")]
+    MissingSeparator,
+    /// The string is too short to hold a checksum, or too long.
+    #[error("invalid length")]
+    InvalidLength,
+    /// The string contains a character outside the bech32 alphabet.
+    #[error("invalid character {0:?}")]
+    InvalidChar(char),
+    /// The checksum does not match.
+    #[error("invalid checksum")]
+    InvalidChecksum,
+    /// The data does not regroup into whole bytes.
+    #[error("invalid padding")]
+    InvalidPadding,
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 0x1f))
+}
+
+/// Regroups `data` from `from`-bit to `to`-bit values.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Bech32Error> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for &value in data {
+        acc = (acc << from) | u32::from(value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(out)
+}
+
+/// Encodes `data` as a lowercase bech32m string with human-readable part `hrp`.
+pub(crate) fn encode(hrp: &str, data: &[u8]) -> String {
+    let hrp = hrp.to_ascii_lowercase();
+    let values = convert_bits(data, 8, 5, true).expect("padding is always valid when encoding");
+    let checksum = polymod(
+        hrp_expand(&hrp)
+            .chain(values.iter().copied())
+            .chain([0u8; 6]),
+    ) ^ BECH32M_CONST;
+
+    let mut out = hrp;
+    out.push('1');
+    out.extend(values.iter().map(|&v| CHARSET[v as usize] as char));
+    out.extend((0..6).map(|i| CHARSET[((checksum >> (5 * (5 - i))) & 0x1f) as usize] as char));
+    out
+}
+
+/// Decodes a bech32m string into its lowercase human-readable part and data bytes.
+pub(crate) fn decode(s: &str) -> Result<(String, Vec<u8>), Bech32Error> {
+    if s.len() > MAX_LENGTH {
+        return Err(Bech32Error::InvalidLength);
+    }
+    if s.chars().any(|c| c.is_ascii_lowercase()) && s.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(Bech32Error::MixedCase);
+    }
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or(Bech32Error::MissingSeparator)?;
+    if separator == 0 {
+        return Err(Bech32Error::MissingSeparator);
+    }
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    if data.len() < 6 {
+        return Err(Bech32Error::InvalidLength);
+    }
+    if let Some(c) = hrp.chars().find(|c| !(33..=126).contains(&(*c as u32))) {
+        return Err(Bech32Error::InvalidChar(c));
+    }
+
+    let values = data
+        .chars()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&b| b as char == c)
+                .map(|v| v as u8)
+                .ok_or(Bech32Error::InvalidChar(c))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    if polymod(hrp_expand(hrp).chain(values.iter().copied())) != BECH32M_CONST {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+
+    let bytes = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+    Ok((hrp.to_string(), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip350_valid_checksums() {
+        for s in [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "?1v759aa",
+        ] {
+            assert!(decode(s).is_ok(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_strings() {
+        assert_eq!(decode("a1lqfn3q"), Err(Bech32Error::InvalidChecksum));
+        assert_eq!(decode("A1lqfn3a"), Err(Bech32Error::MixedCase));
+        assert_eq!(decode("1lqfn3a"), Err(Bech32Error::MissingSeparator));
+        assert_eq!(decode("lqfn3a"), Err(Bech32Error::MissingSeparator));
+        assert_eq!(decode("a1qfn3a"), Err(Bech32Error::InvalidLength));
+        assert_eq!(decode("a1lqfn3b"), Err(Bech32Error::InvalidChar('b')));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u8> = (0..16).collect();
+        let encoded = encode("mtst", &data);
+        assert!(encoded.starts_with("mtst1"));
+        assert_eq!(decode(&encoded), Ok(("mtst".to_string(), data.clone())));
+        assert_eq!(
+            decode(&encoded.to_uppercase()),
+            Ok(("mtst".to_string(), data))
+        );
+    }
+}
@@ -3,19 +3,508 @@
 //! This module provides configuration structures used to initialize
 //! a Miden chain provider for facilitator operations.
 
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use super::MidenChainReference;
 
+/// Default timeout for a single RPC request.
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A Miden node RPC endpoint: an `http` or `https` scheme, a host and a port.
+///
+/// Parsing normalizes the URL: scheme and host are lowercased, a missing port
+/// defaults to the scheme's (443 for `https`, 80 for `http`) and a trailing
+/// `/` is dropped. Paths, queries, fragments and user info are rejected, as
+/// the gRPC client would silently ignore them. The canonical form, used for
+/// `Display` and serialization, omits the port when it is the default.
+///
+/// # Example
+///
+/// ```
+/// use x402_chain_miden::chain::RpcUrl;
+///
+/// let url: RpcUrl = "HTTPS://rpc.testnet.miden.io:443/".parse().unwrap();
+/// assert_eq!(url.to_string(), "https://rpc.testnet.miden.io");
+/// assert_eq!(url.port(), 443);
+/// assert!("grpc://rpc.testnet.miden.io".parse::<RpcUrl>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RpcUrl {
+    tls: bool,
+    host: String,
+    port: u16,
+}
+
+impl RpcUrl {
+    /// Returns the scheme, `https` or `http`.
+    pub fn scheme(&self) -> &'static str {
+        if self.tls { "https" } else { "http" }
+    }
+
+    /// Returns `true` for `https` endpoints.
+    pub fn is_tls(&self) -> bool {
+        self.tls
+    }
+
+    /// Returns the host name or IP address (IPv6 addresses in brackets).
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port, defaulted from the scheme if the URL had none.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    fn default_port(tls: bool) -> u16 {
+        if tls { 443 } else { 80 }
+    }
+}
+
+impl FromStr for RpcUrl {
+    type Err = MidenChainConfigError;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| MidenChainConfigError::InvalidRpcUrl {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        };
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| invalid("missing scheme"))?;
+        let tls = if scheme.eq_ignore_ascii_case("https") {
+            true
+        } else if scheme.eq_ignore_ascii_case("http") {
+            false
+        } else {
+            return Err(invalid("scheme must be http or https"));
+        };
+
+        let authority = rest.strip_suffix('/').unwrap_or(rest);
+        if authority.contains(['/', '?', '#']) {
+            return Err(invalid("must not have a path, query or fragment"));
+        }
+        if authority.contains('@') {
+            return Err(invalid("must not contain user info"));
+        }
+
+        // An IPv6 host is bracketed and contains colons of its own.
+        let port_separator = match authority.rfind(']') {
+            Some(end) => authority[end..].find(':').map(|i| end + i),
+            None => authority.rfind(':'),
+        };
+        let (host, port) = match port_separator {
+            Some(i) => {
+                let port = authority[i + 1..]
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|port| *port != 0)
+                    .ok_or_else(|| invalid("invalid port"))?;
+                (&authority[..i], port)
+            }
+            None => (authority, Self::default_port(tls)),
+        };
+        if host.is_empty() || host == "[]" {
+            return Err(invalid("missing host"));
+        }
+        if let Some(address) = host.strip_prefix('[') {
+            if address
+                .strip_suffix(']')
+                .is_none_or(|inner| inner.contains(['[', ']']))
+            {
+                return Err(invalid("unterminated IPv6 address"));
+            }
+        } else if host.contains([':', '[', ']']) {
+            return Err(invalid("IPv6 address must be in brackets"));
+        }
+
+        Ok(Self {
+            tls,
+            host: host.to_ascii_lowercase(),
+            port,
+        })
+    }
+}
+
+impl TryFrom<&str> for RpcUrl {
+    type Error = MidenChainConfigError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for RpcUrl {
+    type Error = MidenChainConfigError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for RpcUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}", self.scheme(), self.host)?;
+        if self.port != Self::default_port(self.tls) {
+            write!(f, ":{}", self.port)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for RpcUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "miden-client-native")]
+impl From<&RpcUrl> for miden_client::rpc::Endpoint {
+    fn from(url: &RpcUrl) -> Self {
+        Self::new(url.scheme().to_string(), url.host.clone(), Some(url.port))
+    }
+}
+
 /// Configuration for a Miden chain connection.
 ///
 /// This configuration is used to initialize a [`MidenChainProvider`](super::provider::MidenChainProvider)
 /// for facilitator-side operations (verification and settlement).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Only `chainReference` and `rpcUrl` are required; every operational field
+/// is optional, so the original two-field shape still deserializes.
+///
+/// ```json
+/// {
+///   "chainReference": "testnet",
+///   "rpcUrl": "https://rpc.testnet.miden.io",
+///   "timeoutMs": 5000,
+///   "maxRetries": 2,
+///   "fallbackRpcUrls": ["https://rpc2.testnet.example"],
+///   "requireTls": true,
+///   "maxRequestsPerSecond": 20
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MidenChainConfig {
     /// The chain reference (e.g., `testnet`, `mainnet`).
     pub chain_reference: MidenChainReference,
     /// The Miden node RPC endpoint URL.
-    pub rpc_url: String,
+    pub rpc_url: RpcUrl,
+    /// Timeout for a single RPC request, in milliseconds. Defaults to
+    /// [`DEFAULT_RPC_TIMEOUT`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// How many times a failed RPC request is retried (across all endpoints)
+    /// before giving up. Defaults to no retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    /// Endpoints tried, in order, when `rpc_url` fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_urls: Vec<RpcUrl>,
+    /// Rejects plain `http://` endpoints when set.
+    #[serde(default)]
+    pub require_tls: bool,
+    /// Upper bound on outbound RPC requests per second. Unlimited by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_second: Option<u32>,
+}
+
+impl MidenChainConfig {
+    /// Creates a configuration with the given endpoint and default settings.
+    pub fn new(chain_reference: MidenChainReference, rpc_url: RpcUrl) -> Self {
+        Self {
+            chain_reference,
+            rpc_url,
+            timeout_ms: None,
+            max_retries: None,
+            fallback_rpc_urls: Vec::new(),
+            require_tls: false,
+            max_requests_per_second: None,
+        }
+    }
+
+    /// Sets the timeout for a single RPC request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = Some(u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Sets how many times a failed RPC request is retried.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Adds an endpoint to try when the previous ones fail.
+    pub fn with_fallback_rpc_url(mut self, url: RpcUrl) -> Self {
+        self.fallback_rpc_urls.push(url);
+        self
+    }
+
+    /// Requires every endpoint to use `https://`.
+    pub fn with_require_tls(mut self, require_tls: bool) -> Self {
+        self.require_tls = require_tls;
+        self
+    }
+
+    /// Limits outbound RPC requests per second.
+    pub fn with_max_requests_per_second(mut self, max_requests_per_second: u32) -> Self {
+        self.max_requests_per_second = Some(max_requests_per_second);
+        self
+    }
+
+    /// Returns the RPC request timeout, falling back to [`DEFAULT_RPC_TIMEOUT`].
+    pub fn timeout(&self) -> Duration {
+        self.timeout_ms
+            .map_or(DEFAULT_RPC_TIMEOUT, Duration::from_millis)
+    }
+
+    /// Returns the primary endpoint followed by the fallbacks.
+    pub fn rpc_urls(&self) -> impl Iterator<Item = &RpcUrl> {
+        std::iter::once(&self.rpc_url).chain(&self.fallback_rpc_urls)
+    }
+
+    /// Checks the configuration, reporting every problem rather than the
+    /// first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns all [`MidenChainConfigError`]s found.
+    pub fn validate(&self) -> Result<(), Vec<MidenChainConfigError>> {
+        let mut errors = Vec::new();
+        if self.require_tls {
+            errors.extend(
+                self.rpc_urls()
+                    .filter(|url| !url.is_tls())
+                    .map(|url| MidenChainConfigError::TlsRequired(url.to_string())),
+            );
+        }
+        if self.timeout_ms == Some(0) {
+            errors.push(MidenChainConfigError::ZeroTimeout);
+        }
+        if self.max_requests_per_second == Some(0) {
+            errors.push(MidenChainConfigError::ZeroRateLimit);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// A problem found by [`MidenChainConfig::validate`] or when parsing an
+/// [`RpcUrl`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MidenChainConfigError {
+    /// An RPC URL is malformed.
+    #[error("Invalid RPC URL '{url}': {reason}")]
+    InvalidRpcUrl { url: String, reason: String },
+    /// An RPC URL uses plain HTTP although TLS is required.
+    #[error("RPC URL '{0}' must use https when requireTls is set")]
+    TlsRequired(String),
+    /// The request timeout is zero.
+    #[error("RPC timeout must be greater than zero")]
+    ZeroTimeout,
+    /// The rate limit is zero requests per second.
+    #[error("maxRequestsPerSecond must be greater than zero")]
+    ZeroRateLimit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> RpcUrl {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_minimal_shape_deserializes() {
+        let json = r#"{"chainReference": "testnet", "rpcUrl": "https://rpc.testnet.miden.io"}"#;
+        let config: MidenChainConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config,
+            MidenChainConfig::new(
+                MidenChainReference::testnet(),
+                url("https://rpc.testnet.miden.io")
+            )
+        );
+        assert_eq!(config.timeout(), DEFAULT_RPC_TIMEOUT);
+        assert_eq!(config.rpc_urls().count(), 1);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_full_shape_roundtrip() {
+        let config =
+            MidenChainConfig::new(MidenChainReference::testnet(), url("https://a.example"))
+                .with_timeout(Duration::from_secs(5))
+                .with_max_retries(2)
+                .with_fallback_rpc_url(url("https://b.example"))
+                .with_require_tls(true)
+                .with_max_requests_per_second(20);
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["timeoutMs"], 5000);
+        assert_eq!(json["maxRetries"], 2);
+        assert_eq!(json["fallbackRpcUrls"][0], "https://b.example");
+        assert_eq!(json["requireTls"], true);
+        assert_eq!(json["maxRequestsPerSecond"], 20);
+
+        let decoded: MidenChainConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, config);
+        assert_eq!(
+            decoded
+                .rpc_urls()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["https://a.example", "https://b.example"]
+        );
+    }
+
+    #[test]
+    fn test_minimal_shape_serializes_without_unset_fields() {
+        let config =
+            MidenChainConfig::new(MidenChainReference::testnet(), url("https://a.example"));
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json.get("timeoutMs").is_none());
+        assert!(json.get("fallbackRpcUrls").is_none());
+    }
+
+    #[test]
+    fn test_config_with_malformed_url_fails_to_deserialize() {
+        let json = r#"{"chainReference": "testnet", "rpcUrl": "grpc://rpc.testnet.miden.io"}"#;
+        let err = serde_json::from_str::<MidenChainConfig>(json).unwrap_err();
+        assert!(
+            err.to_string().contains("scheme must be http or https"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut config =
+            MidenChainConfig::new(MidenChainReference::testnet(), url("http://a.example"))
+                .with_fallback_rpc_url(url("https://b.example"))
+                .with_fallback_rpc_url(url("http://c.example:8080"))
+                .with_require_tls(true)
+                .with_max_requests_per_second(0);
+        config.timeout_ms = Some(0);
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                MidenChainConfigError::TlsRequired("http://a.example".to_string()),
+                MidenChainConfigError::TlsRequired("http://c.example:8080".to_string()),
+                MidenChainConfigError::ZeroTimeout,
+                MidenChainConfigError::ZeroRateLimit,
+            ]
+        );
+
+        // Plain HTTP is fine unless TLS is required.
+        let local = MidenChainConfig::new(
+            MidenChainReference::testnet(),
+            url("http://localhost:57291"),
+        );
+        assert!(local.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rpc_url_normalizes() {
+        let cases = [
+            (
+                "https://rpc.testnet.miden.io",
+                "https://rpc.testnet.miden.io",
+                443,
+            ),
+            (
+                "HTTPS://RPC.Testnet.Miden.io/",
+                "https://rpc.testnet.miden.io",
+                443,
+            ),
+            (
+                "https://rpc.testnet.miden.io:443",
+                "https://rpc.testnet.miden.io",
+                443,
+            ),
+            ("http://localhost", "http://localhost", 80),
+            ("http://localhost:57291/", "http://localhost:57291", 57291),
+            ("http://127.0.0.1:8080", "http://127.0.0.1:8080", 8080),
+            ("http://[::1]", "http://[::1]", 80),
+            ("http://[::1]:57291", "http://[::1]:57291", 57291),
+        ];
+        for (input, canonical, port) in cases {
+            let url = url(input);
+            assert_eq!(url.to_string(), canonical, "{input}");
+            assert_eq!(url.port(), port, "{input}");
+            assert_eq!(RpcUrl::try_from(canonical.to_string()).unwrap(), url);
+            assert_eq!(
+                serde_json::to_value(&url).unwrap(),
+                serde_json::Value::from(canonical)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rpc_url_rejects_malformed() {
+        let cases = [
+            ("rpc.testnet.miden.io", "missing scheme"),
+            (
+                "grpc://rpc.testnet.miden.io",
+                "scheme must be http or https",
+            ),
+            ("ftp://files.example", "scheme must be http or https"),
+            ("https://", "missing host"),
+            ("https://:443", "missing host"),
+            (
+                "https://rpc.testnet.miden.io/v1",
+                "must not have a path, query or fragment",
+            ),
+            (
+                "https://rpc.testnet.miden.io?x=1",
+                "must not have a path, query or fragment",
+            ),
+            (
+                "https://user@rpc.testnet.miden.io",
+                "must not contain user info",
+            ),
+            ("https://rpc.testnet.miden.io:", "invalid port"),
+            ("https://rpc.testnet.miden.io:0", "invalid port"),
+            ("https://rpc.testnet.miden.io:65536", "invalid port"),
+            ("https://rpc.testnet.miden.io:port", "invalid port"),
+            ("http://[]", "missing host"),
+            ("http://[::1", "unterminated IPv6 address"),
+            ("http://[::1:57291", "unterminated IPv6 address"),
+            ("http://::1", "IPv6 address must be in brackets"),
+            ("http://::1]", "IPv6 address must be in brackets"),
+        ];
+        for (input, expected) in cases {
+            match input.parse::<RpcUrl>() {
+                Err(MidenChainConfigError::InvalidRpcUrl { url, reason }) => {
+                    assert_eq!((url.as_str(), reason.as_str()), (input, expected));
+                }
+                other => panic!("{input}: unexpected {other:?}"),
+            }
+        }
+    }
 }
@@ -8,12 +8,17 @@
 //! - [`MidenTokenDeployment`] - Token (faucet) deployment info
 //! - [`MidenChainConfig`] - Configuration for connecting to a Miden node
 
+mod bech32;
+
 pub mod types;
 pub use types::*;
 
 pub mod config;
 pub use config::*;
 
+pub mod registry;
+pub use registry::*;
+
 #[cfg(feature = "facilitator")]
 pub mod provider;
 #[cfg(feature = "facilitator")]
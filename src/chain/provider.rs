@@ -5,7 +5,7 @@
 
 use x402_types::chain::{ChainId, ChainProviderOps};
 
-use super::{MidenChainConfig, MidenChainReference};
+use super::{MidenChainConfig, MidenChainConfigError, MidenChainReference, RpcUrl};
 
 /// Provider for interacting with a Miden node.
 ///
@@ -20,54 +20,95 @@ use super::{MidenChainConfig, MidenChainReference};
 /// ```ignore
 /// use x402_chain_miden::chain::{MidenChainConfig, MidenChainProvider, MidenChainReference};
 ///
-/// let config = MidenChainConfig {
-///     chain_reference: MidenChainReference::testnet(),
-///     rpc_url: "https://rpc.testnet.miden.io".to_string(),
-/// };
-/// let provider = MidenChainProvider::from_config(&config);
+/// let config = MidenChainConfig::new(
+///     MidenChainReference::testnet(),
+///     "https://rpc.testnet.miden.io".parse()?,
+/// )
+/// .with_max_retries(2);
+/// let provider = MidenChainProvider::try_from_config(&config)?;
 /// ```
 pub struct MidenChainProvider {
-    chain_reference: MidenChainReference,
-    rpc_url: String,
+    config: MidenChainConfig,
+    /// The primary endpoint followed by the fallbacks.
     #[cfg(feature = "miden-client-native")]
-    rpc_client: std::sync::Arc<miden_client::rpc::GrpcClient>,
+    endpoints: Vec<RpcEndpoint>,
+    #[cfg(feature = "miden-client-native")]
+    throttle: Option<RequestThrottle>,
+}
+
+/// A gRPC client for one RPC endpoint.
+#[cfg(feature = "miden-client-native")]
+struct RpcEndpoint {
+    url: RpcUrl,
+    client: std::sync::Arc<miden_client::rpc::GrpcClient>,
     /// Tracks whether the genesis commitment has already been set on the
     /// gRPC client, so we skip the RPC call on subsequent invocations.
-    #[cfg(feature = "miden-client-native")]
     genesis_committed: std::sync::atomic::AtomicBool,
 }
 
 impl MidenChainProvider {
-    /// Creates a new provider from configuration.
+    /// Creates a new provider from configuration, without validating it.
     ///
     /// When the `miden-client-native` feature is enabled, this also constructs
-    /// a gRPC client connected to the configured RPC endpoint.
+    /// a gRPC client for the configured RPC endpoint and each fallback.
     pub fn from_config(config: &MidenChainConfig) -> Self {
+        Self::build(config)
+    }
+
+    /// Validates the configuration and creates a provider from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenProviderError::InvalidConfig`] listing every problem
+    /// found by [`MidenChainConfig::validate`].
+    pub fn try_from_config(config: &MidenChainConfig) -> Result<Self, MidenProviderError> {
+        config
+            .validate()
+            .map_err(MidenProviderError::InvalidConfig)?;
+        Ok(Self::build(config))
+    }
+
+    fn build(config: &MidenChainConfig) -> Self {
         Self {
-            chain_reference: config.chain_reference.clone(),
-            rpc_url: config.rpc_url.clone(),
+            config: config.clone(),
             #[cfg(feature = "miden-client-native")]
-            rpc_client: {
-                let endpoint = config
-                    .rpc_url
-                    .as_str()
-                    .try_into()
-                    .expect("RPC URL must be a valid endpoint");
-                std::sync::Arc::new(miden_client::rpc::GrpcClient::new(&endpoint, 10_000))
-            },
+            endpoints: config
+                .rpc_urls()
+                .map(|url| RpcEndpoint::connect(url, config.timeout()))
+                .collect(),
             #[cfg(feature = "miden-client-native")]
-            genesis_committed: std::sync::atomic::AtomicBool::new(false),
+            throttle: config
+                .max_requests_per_second
+                .map(RequestThrottle::per_second),
         }
     }
 
     /// Returns the chain reference.
     pub fn chain_reference(&self) -> &MidenChainReference {
-        &self.chain_reference
+        &self.config.chain_reference
+    }
+
+    /// Returns the primary RPC URL.
+    pub fn rpc_url(&self) -> &RpcUrl {
+        &self.config.rpc_url
+    }
+
+    /// Returns the configuration the provider was built from.
+    pub fn config(&self) -> &MidenChainConfig {
+        &self.config
     }
+}
 
-    /// Returns the RPC URL.
-    pub fn rpc_url(&self) -> &str {
-        &self.rpc_url
+#[cfg(feature = "miden-client-native")]
+impl RpcEndpoint {
+    fn connect(url: &RpcUrl, timeout: std::time::Duration) -> Self {
+        let endpoint = miden_client::rpc::Endpoint::from(url);
+        let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        Self {
+            url: url.clone(),
+            client: std::sync::Arc::new(miden_client::rpc::GrpcClient::new(&endpoint, timeout_ms)),
+            genesis_committed: std::sync::atomic::AtomicBool::new(false),
+        }
     }
 
     /// Ensures the gRPC client has the genesis commitment set.
@@ -75,7 +116,6 @@ impl MidenChainProvider {
     /// Uses an `AtomicBool` to skip the RPC call on subsequent invocations.
     /// The first call fetches the genesis block header and sets the commitment;
     /// all later calls return immediately.
-    #[cfg(feature = "miden-client-native")]
     async fn ensure_genesis_commitment(&self) -> Result<(), MidenProviderError> {
         use std::sync::atomic::Ordering;
 
@@ -88,7 +128,7 @@ impl MidenChainProvider {
         use miden_protocol::block::BlockNumber;
 
         let (genesis_header, _) = self
-            .rpc_client
+            .client
             .get_block_header_by_number(Some(BlockNumber::GENESIS), false)
             .await
             .map_err(|e| {
@@ -97,7 +137,7 @@ impl MidenChainProvider {
                 ))
             })?;
 
-        self.rpc_client
+        self.client
             .set_genesis_commitment(genesis_header.commitment())
             .await
             .map_err(|e| {
@@ -109,13 +149,44 @@ impl MidenChainProvider {
         self.genesis_committed.store(true, Ordering::Release);
         Ok(())
     }
+}
 
+/// Spaces outbound requests to stay under a requests-per-second limit.
+#[cfg(any(feature = "miden-client-native", test))]
+struct RequestThrottle {
+    interval: std::time::Duration,
+    next_slot: tokio::sync::Mutex<Option<tokio::time::Instant>>,
+}
+
+#[cfg(any(feature = "miden-client-native", test))]
+impl RequestThrottle {
+    fn per_second(max_requests_per_second: u32) -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(1) / max_requests_per_second.max(1),
+            next_slot: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Waits until the next request slot is free and claims it.
+    async fn wait(&self) {
+        let mut next_slot = self.next_slot.lock().await;
+        let now = tokio::time::Instant::now();
+        let slot = next_slot.map_or(now, |slot| slot.max(now));
+        tokio::time::sleep_until(slot).await;
+        *next_slot = Some(slot + self.interval);
+    }
+}
+
+impl MidenChainProvider {
     /// Queries the balance of a specific asset for a given account.
     ///
     /// Returns the balance as a u64 in the token's smallest unit.
     ///
     /// This queries the account via `get_account_details` RPC and inspects the
     /// vault for the given faucet. Only public accounts expose their vault state.
+    ///
+    /// Failed RPC calls move on to the next fallback endpoint; the whole list
+    /// is tried `maxRetries + 1` times before the last error is returned.
     pub async fn get_account_balance(
         &self,
         account_id: &str,
@@ -126,9 +197,6 @@ impl MidenChainProvider {
             use miden_client::rpc::NodeRpcClient;
             use miden_protocol::account::AccountId;
 
-            // Ensure genesis commitment is set before querying
-            self.ensure_genesis_commitment().await?;
-
             let account = AccountId::from_hex(account_id).map_err(|e| {
                 MidenProviderError::QueryError(format!("Invalid account ID '{account_id}': {e}"))
             })?;
@@ -138,21 +206,50 @@ impl MidenChainProvider {
 
             #[cfg(feature = "tracing")]
             tracing::info!(
-                %account_id,
-                %faucet_id,
-                rpc_url = %self.rpc_url,
+                account_id = %super::MidenAccountAddress::from_account_id(account).short(),
+                faucet_id = %super::MidenAccountAddress::from_account_id(faucet).short(),
+                rpc_url = %self.config.rpc_url,
                 "Querying account balance via RPC"
             );
 
-            let fetched = self
-                .rpc_client
-                .get_account_details(account)
-                .await
-                .map_err(|e| {
-                    MidenProviderError::QueryError(format!(
-                        "RPC get_account_details failed for '{account_id}': {e}"
-                    ))
-                })?;
+            let attempts = self.config.max_retries.unwrap_or(0).saturating_add(1);
+            let mut fetched = None;
+            let mut last_error = None;
+            'attempts: for _ in 0..attempts {
+                for endpoint in &self.endpoints {
+                    if let Some(throttle) = &self.throttle {
+                        throttle.wait().await;
+                    }
+                    // Ensure genesis commitment is set before querying
+                    if let Err(e) = endpoint.ensure_genesis_commitment().await {
+                        last_error = Some(e);
+                        continue;
+                    }
+                    match endpoint.client.get_account_details(account).await {
+                        Ok(details) => {
+                            fetched = Some(details);
+                            break 'attempts;
+                        }
+                        Err(e) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(
+                                rpc_url = %endpoint.url,
+                                error = %e,
+                                "get_account_details failed"
+                            );
+                            last_error = Some(MidenProviderError::QueryError(format!(
+                                "RPC get_account_details failed for '{account_id}' via {}: {e}",
+                                endpoint.url
+                            )));
+                        }
+                    }
+                }
+            }
+            let Some(fetched) = fetched else {
+                return Err(last_error.unwrap_or_else(|| {
+                    MidenProviderError::ConnectionError("No RPC endpoint configured".to_string())
+                }));
+            };
 
             // Only public accounts expose their vault
             let balance = match fetched.account() {
@@ -202,7 +299,7 @@ impl ChainProviderOps for MidenChainProvider {
     }
 
     fn chain_id(&self) -> ChainId {
-        self.chain_reference.as_chain_id()
+        self.config.chain_reference.as_chain_id()
     }
 }
 
@@ -220,4 +317,63 @@ pub enum MidenProviderError {
     /// Failed to query account state.
     #[error("Query error: {0}")]
     QueryError(String),
+
+    /// The chain configuration is invalid.
+    #[error("Invalid chain config: {}", join_config_errors(.0))]
+    InvalidConfig(Vec<MidenChainConfigError>),
+}
+
+fn join_config_errors(errors: &[MidenChainConfigError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_config_rejects_invalid_config() {
+        let config = MidenChainConfig::new(
+            MidenChainReference::testnet(),
+            "http://rpc.testnet.miden.io".parse().unwrap(),
+        )
+        .with_require_tls(true)
+        .with_max_requests_per_second(0);
+        let Err(MidenProviderError::InvalidConfig(errors)) =
+            MidenChainProvider::try_from_config(&config)
+        else {
+            panic!("invalid config should be rejected");
+        };
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_try_from_config_keeps_config() {
+        let config = MidenChainConfig::new(
+            MidenChainReference::testnet(),
+            "https://rpc.testnet.miden.io".parse().unwrap(),
+        )
+        .with_max_retries(3);
+        let provider = MidenChainProvider::try_from_config(&config).unwrap();
+        assert_eq!(provider.config(), &config);
+        assert_eq!(
+            provider.rpc_url().to_string(),
+            "https://rpc.testnet.miden.io"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_throttle_spaces_requests() {
+        let throttle = RequestThrottle::per_second(50);
+        let start = tokio::time::Instant::now();
+        for _ in 0..3 {
+            throttle.wait().await;
+        }
+        // The first request goes out at once, the next two 20ms apart.
+        assert!(start.elapsed() >= std::time::Duration::from_millis(40));
+    }
 }
@@ -0,0 +1,346 @@
+//! A registry of known token deployments.
+//!
+//! Maps `(network, symbol)` pairs and faucet IDs to [`MidenTokenDeployment`]s,
+//! so applications can configure accepted tokens by name ("USDC on testnet")
+//! instead of hand-maintaining faucet IDs.
+//!
+//! Servers can price resources with `V2MidenExact::price_tag_from_registry`,
+//! and payers can cap spending with
+//! `SpendingLimits::with_registered_max_amount`, both in whole tokens.
+//!
+//! # Example
+//!
+//! ```
+//! use x402_chain_miden::chain::{MidenChainReference, MidenTokenRegistry};
+//!
+//! let registry = MidenTokenRegistry::known();
+//! let price = registry.parse_amount(&MidenChainReference::testnet(), "USDC", "1.50")?;
+//! assert_eq!(price.amount, 1_500_000);
+//! # Ok::<(), x402_chain_miden::chain::MidenTokenRegistryError>(())
+//! ```
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    MidenAccountAddress, MidenAmountParseError, MidenChainReference, MidenDeployedTokenAmount,
+    MidenTokenDeployment,
+};
+
+/// A set of token deployments, looked up by symbol or faucet ID.
+///
+/// Faucet IDs are unique within a registry, as are symbols within one
+/// network (compared case-insensitively). Deployments without a symbol can
+/// only be found by faucet ID.
+///
+/// Serializes as `{"tokens": [...]}`, using the
+/// [`MidenTokenDeployment`] wire shape for each entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MidenTokenRegistry {
+    tokens: Vec<MidenTokenDeployment>,
+}
+
+impl MidenTokenRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the known deployments of this crate.
+    ///
+    /// Contains testnet USDC (honoring `MIDEN_TESTNET_FAUCET_ID`), and
+    /// mainnet USDC only when `MIDEN_MAINNET_FAUCET_ID` is configured.
+    pub fn known() -> Self {
+        let mut registry = Self::new();
+        let known = std::iter::once(MidenTokenDeployment::testnet_usdc())
+            .chain(MidenTokenDeployment::mainnet_usdc().ok());
+        for deployment in known {
+            registry
+                .register(deployment)
+                .expect("known deployments do not collide");
+        }
+        registry
+    }
+
+    /// Adds a deployment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenTokenRegistryError::DuplicateFaucet`] if the faucet is
+    /// already registered, and [`MidenTokenRegistryError::DuplicateSymbol`] if
+    /// another deployment on the same network has the same symbol.
+    pub fn register(
+        &mut self,
+        deployment: MidenTokenDeployment,
+    ) -> Result<(), MidenTokenRegistryError> {
+        if self.by_faucet_id(&deployment.faucet_id).is_some() {
+            return Err(MidenTokenRegistryError::DuplicateFaucet(
+                deployment.faucet_id,
+            ));
+        }
+        if let Some(symbol) = &deployment.symbol
+            && self
+                .by_symbol(&deployment.chain_reference, symbol)
+                .is_some()
+        {
+            return Err(MidenTokenRegistryError::DuplicateSymbol {
+                symbol: symbol.clone(),
+                network: deployment.chain_reference,
+            });
+        }
+        self.tokens.push(deployment);
+        Ok(())
+    }
+
+    /// Looks up the deployment with `symbol` on `network`, ignoring case.
+    pub fn by_symbol(
+        &self,
+        network: &MidenChainReference,
+        symbol: &str,
+    ) -> Option<&MidenTokenDeployment> {
+        self.tokens.iter().find(|token| {
+            token.chain_reference == *network
+                && token
+                    .symbol
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(symbol))
+        })
+    }
+
+    /// Looks up the deployment issued by `faucet_id`.
+    pub fn by_faucet_id(&self, faucet_id: &MidenAccountAddress) -> Option<&MidenTokenDeployment> {
+        self.tokens
+            .iter()
+            .find(|token| token.faucet_id == *faucet_id)
+    }
+
+    /// Parses a decimal amount (e.g. `"1.50"`) of the token with `symbol` on
+    /// `network`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenTokenRegistryError::UnknownToken`] if no such token is
+    /// registered, and [`MidenTokenRegistryError::Amount`] if the amount does
+    /// not parse.
+    pub fn parse_amount(
+        &self,
+        network: &MidenChainReference,
+        symbol: &str,
+        amount: &str,
+    ) -> Result<MidenDeployedTokenAmount, MidenTokenRegistryError> {
+        let token = self.by_symbol(network, symbol).ok_or_else(|| {
+            MidenTokenRegistryError::UnknownToken {
+                symbol: symbol.to_string(),
+                network: network.clone(),
+            }
+        })?;
+        Ok(token.parse(amount)?)
+    }
+
+    /// Returns the registered deployments in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &MidenTokenDeployment> {
+        self.tokens.iter()
+    }
+
+    /// Returns the number of registered deployments.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns `true` if no deployment is registered.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Loads a registry from a JSON file.
+    ///
+    /// Entries go through [`register`](Self::register), so a file with
+    /// duplicate faucets or symbols is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or contains
+    /// duplicates.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, MidenTokenRegistryError> {
+        let contents = std::fs::read_to_string(path)?;
+        let loaded: Self = serde_json::from_str(&contents)?;
+        let mut registry = Self::new();
+        for deployment in loaded.tokens {
+            registry.register(deployment)?;
+        }
+        Ok(registry)
+    }
+
+    /// Saves the registry to a JSON file, replacing any existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_json_file(&self, path: impl AsRef<Path>) -> Result<(), MidenTokenRegistryError> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Error returned by [`MidenTokenRegistry`] operations.
+#[derive(Debug, thiserror::Error)]
+pub enum MidenTokenRegistryError {
+    /// The faucet is already registered.
+    #[error("Faucet {0} is already registered")]
+    DuplicateFaucet(MidenAccountAddress),
+    /// Another deployment on the network already uses the symbol.
+    #[error("Symbol {symbol} is already registered on {network}")]
+    DuplicateSymbol {
+        symbol: String,
+        network: MidenChainReference,
+    },
+    /// No deployment with the symbol is registered on the network.
+    #[error("No token {symbol} registered on {network}")]
+    UnknownToken {
+        symbol: String,
+        network: MidenChainReference,
+    },
+    /// The amount could not be parsed for the token.
+    #[error(transparent)]
+    Amount(#[from] MidenAmountParseError),
+    /// The registry file could not be read or written.
+    #[error("Registry file error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The registry file is not valid JSON for a registry.
+    #[error("Invalid registry file: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(faucet: &str, network: MidenChainReference, symbol: &str) -> MidenTokenDeployment {
+        MidenTokenDeployment::new(network, faucet.parse().unwrap(), 6).with_symbol(symbol)
+    }
+
+    const FAUCET_A: &str = "0x37d5977a8e16d8205a360820f0230f";
+    const FAUCET_B: &str = "0x37d5977a8e16d8a05a360820f0230f";
+
+    #[test]
+    fn test_lookups() {
+        let mut registry = MidenTokenRegistry::new();
+        registry
+            .register(token(FAUCET_A, MidenChainReference::testnet(), "USDC"))
+            .unwrap();
+        registry
+            .register(token(FAUCET_B, MidenChainReference::testnet(), "MTK"))
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let testnet = MidenChainReference::testnet();
+        let usdc = registry.by_symbol(&testnet, "usdc").unwrap();
+        assert_eq!(usdc.faucet_id.to_hex(), FAUCET_A);
+        assert!(
+            registry
+                .by_symbol(&MidenChainReference::mainnet(), "USDC")
+                .is_none()
+        );
+
+        let faucet_b: MidenAccountAddress = FAUCET_B.parse().unwrap();
+        assert_eq!(
+            registry.by_faucet_id(&faucet_b).unwrap().symbol.as_deref(),
+            Some("MTK")
+        );
+
+        let amount = registry.parse_amount(&testnet, "MTK", "1.50").unwrap();
+        assert_eq!(amount.amount, 1_500_000);
+        assert_eq!(amount.token.faucet_id, faucet_b);
+        assert!(matches!(
+            registry.parse_amount(&testnet, "DAI", "1"),
+            Err(MidenTokenRegistryError::UnknownToken { .. })
+        ));
+        assert!(matches!(
+            registry.parse_amount(&testnet, "USDC", "abc"),
+            Err(MidenTokenRegistryError::Amount(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_registration() {
+        let mut registry = MidenTokenRegistry::new();
+        registry
+            .register(token(FAUCET_A, MidenChainReference::testnet(), "USDC"))
+            .unwrap();
+
+        assert!(matches!(
+            registry.register(token(FAUCET_A, MidenChainReference::testnet(), "OTHER")),
+            Err(MidenTokenRegistryError::DuplicateFaucet(_))
+        ));
+        assert!(matches!(
+            registry.register(token(FAUCET_B, MidenChainReference::testnet(), "usdc")),
+            Err(MidenTokenRegistryError::DuplicateSymbol { .. })
+        ));
+        // The same symbol on another network is fine.
+        registry
+            .register(token(FAUCET_B, MidenChainReference::mainnet(), "USDC"))
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_known_registry_has_testnet_usdc() {
+        let registry = MidenTokenRegistry::known();
+        let usdc = registry
+            .by_symbol(&MidenChainReference::testnet(), "USDC")
+            .unwrap();
+        assert_eq!(usdc, &MidenTokenDeployment::testnet_usdc());
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let mut registry = MidenTokenRegistry::new();
+        registry
+            .register(token(FAUCET_A, MidenChainReference::testnet(), "USDC").with_name("USD Coin"))
+            .unwrap();
+        registry
+            .register(MidenTokenDeployment::new(
+                MidenChainReference::testnet(),
+                FAUCET_B.parse().unwrap(),
+                8,
+            ))
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("x402-miden-registry-{}.json", std::process::id()));
+        registry.save_json_file(&path).unwrap();
+        let loaded = MidenTokenRegistry::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        let usdc = loaded
+            .by_symbol(&MidenChainReference::testnet(), "USDC")
+            .unwrap();
+        assert_eq!(usdc.name.as_deref(), Some("USD Coin"));
+        let faucet_b: MidenAccountAddress = FAUCET_B.parse().unwrap();
+        assert_eq!(loaded.by_faucet_id(&faucet_b).unwrap().decimals, 8);
+    }
+
+    #[test]
+    fn test_file_with_duplicates_rejected() {
+        let json = format!(
+            r#"{{"tokens": [
+                {{"chainReference": "testnet", "faucetId": "{FAUCET_A}", "decimals": 6}},
+                {{"chainReference": "testnet", "faucetId": "{FAUCET_A}", "decimals": 6}}
+            ]}}"#
+        );
+        let path = std::env::temp_dir().join(format!(
+            "x402-miden-registry-dup-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, json).unwrap();
+        let result = MidenTokenRegistry::from_json_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(MidenTokenRegistryError::DuplicateFaucet(_))
+        ));
+    }
+}
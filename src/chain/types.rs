@@ -20,13 +20,21 @@ pub const MIDEN_NAMESPACE: &str = "miden";
 /// Miden account IDs are 120-bit (15 bytes) identifiers. This wrapper
 /// ensures consistent serialization in the x402 protocol wire format.
 ///
+/// Parsing (including deserialization) also accepts the bech32 address form
+/// shown by Miden tooling, e.g. `mtst1...` on testnet. Hex stays the
+/// canonical output; use [`to_bech32`](Self::to_bech32) for the bech32 form.
+///
+/// Parsed IDs are checked against the account ID layout (see
+/// [`validate`](Self::validate)); the `*_unchecked` constructors skip this
+/// for callers that only shuttle wire-format bytes around.
+///
 /// # Example
 ///
 /// ```
 /// use x402_chain_miden::chain::MidenAccountAddress;
 ///
 /// // 15 bytes = 30 hex chars
-/// let addr: MidenAccountAddress = "0xabcdef1234567890abcdef12345678".parse().unwrap();
+/// let addr: MidenAccountAddress = "0xabcdef12345678902bcdef12345678".parse().unwrap();
 /// assert!(addr.to_string().starts_with("0x"));
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -35,13 +43,39 @@ pub struct MidenAccountAddress([u8; MIDEN_ACCOUNT_ID_BYTE_LEN]);
 /// The expected byte length of a Miden account ID (120 bits = 15 bytes).
 pub const MIDEN_ACCOUNT_ID_BYTE_LEN: usize = 15;
 
+/// Leading data byte of a bech32 address that holds a bare account ID.
+const BECH32_ACCOUNT_ID_ADDRESS_TYPE: u8 = 232;
+
+/// Index of the byte holding the version, account type and storage mode
+/// (the least significant byte of the ID prefix).
+const ACCOUNT_ID_METADATA_BYTE: usize = 7;
+
+/// Largest prefix that is still a valid Goldilocks field element.
+const ACCOUNT_ID_MAX_PREFIX: u64 = 0xFFFF_FFFF_0000_0000;
+
+/// Storage mode bits that no storage mode uses.
+const ACCOUNT_ID_INVALID_STORAGE_MODE: u8 = 0b11;
+
 impl MidenAccountAddress {
     /// Creates a new MidenAccountAddress from raw bytes.
     ///
     /// # Errors
     ///
-    /// Returns an error if the input is not exactly 15 bytes.
+    /// Returns an error if the input is not exactly 15 bytes or is not a
+    /// structurally valid account ID (see [`validate`](Self::validate)).
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, MidenAddressParseError> {
+        let address = Self::from_bytes_unchecked(bytes)?;
+        address.validate()?;
+        Ok(address)
+    }
+
+    /// Creates a new MidenAccountAddress from raw bytes, checking only the
+    /// length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not exactly 15 bytes.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, MidenAddressParseError> {
         let arr: [u8; MIDEN_ACCOUNT_ID_BYTE_LEN] =
             bytes
                 .try_into()
@@ -52,6 +86,89 @@ impl MidenAccountAddress {
         Ok(Self(arr))
     }
 
+    /// Decodes a 30-digit hex address, with an optional `0x` prefix, in a
+    /// const context.
+    ///
+    /// Checks only the hex digits and the length, not
+    /// [`validate`](Self::validate). Prefer the [`miden_address!`] macro,
+    /// which forces compile-time evaluation so a typo fails the build.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time, when evaluated in a const context) if the
+    /// string is not 15 bytes of hex.
+    ///
+    /// [`miden_address!`]: crate::miden_address
+    pub const fn from_hex_const(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        let offset = if bytes.len() >= 2 && bytes[0] == b'0' && bytes[1] == b'x' {
+            2
+        } else {
+            0
+        };
+        if bytes.len() - offset != 2 * MIDEN_ACCOUNT_ID_BYTE_LEN {
+            panic!("Miden address must be 30 hex digits (15 bytes)");
+        }
+        let mut out = [0u8; MIDEN_ACCOUNT_ID_BYTE_LEN];
+        let mut i = 0;
+        while i < MIDEN_ACCOUNT_ID_BYTE_LEN {
+            let high = const_hex_digit(bytes[offset + 2 * i]);
+            let low = const_hex_digit(bytes[offset + 2 * i + 1]);
+            out[i] = (high << 4) | low;
+            i += 1;
+        }
+        Self(out)
+    }
+
+    /// Parses a hex or bech32 address like [`FromStr`], but without
+    /// [`validate`](Self::validate).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a well-formed 15-byte address.
+    pub fn parse_unchecked(s: &str) -> Result<Self, MidenAddressParseError> {
+        if Self::looks_like_bech32(s) {
+            return Self::decode_bech32(s, None).map(|(address, _)| address);
+        }
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let bytes =
+            hex::decode(s).map_err(|e| MidenAddressParseError::InvalidHex(e.to_string()))?;
+        Self::from_bytes_unchecked(&bytes)
+    }
+
+    /// Checks that the bytes follow the account ID layout.
+    ///
+    /// Always checks the version, storage mode, suffix and prefix bits. With
+    /// the `miden-native` feature the ID is additionally parsed by
+    /// `AccountId::from_hex`. Every 2-bit account type is defined, so the
+    /// type bits need no check of their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns the variant of [`MidenAddressParseError`] naming the first
+    /// violated rule.
+    pub fn validate(&self) -> Result<(), MidenAddressParseError> {
+        let metadata = self.0[ACCOUNT_ID_METADATA_BYTE];
+        let version = metadata & 0x0f;
+        if version != 0 {
+            return Err(MidenAddressParseError::UnsupportedVersion(version));
+        }
+        if metadata >> 6 == ACCOUNT_ID_INVALID_STORAGE_MODE {
+            return Err(MidenAddressParseError::InvalidStorageMode(metadata >> 6));
+        }
+        if self.0[ACCOUNT_ID_METADATA_BYTE + 1] & 0x80 != 0 {
+            return Err(MidenAddressParseError::InvalidSuffix);
+        }
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&self.0[..8]);
+        if u64::from_be_bytes(prefix) > ACCOUNT_ID_MAX_PREFIX {
+            return Err(MidenAddressParseError::InvalidPrefix);
+        }
+        #[cfg(feature = "miden-native")]
+        self.to_account_id()?;
+        Ok(())
+    }
+
     /// Returns the raw bytes of the account ID.
     pub fn as_bytes(&self) -> &[u8; MIDEN_ACCOUNT_ID_BYTE_LEN] {
         &self.0
@@ -61,23 +178,233 @@ impl MidenAccountAddress {
     pub fn to_hex(&self) -> String {
         format!("0x{}", hex::encode(self.0))
     }
+
+    /// Returns a truncated form for logs and UIs, e.g. `0x37d5…230f`.
+    ///
+    /// Not parseable; use [`to_hex`](Self::to_hex) wherever the address
+    /// must be read back.
+    pub fn short(&self) -> String {
+        let hex = hex::encode(self.0);
+        format!("0x{}…{}", &hex[..4], &hex[hex.len() - 4..])
+    }
+
+    /// Returns the hex form followed by a CRC-32 checksum of the account ID
+    /// bytes as 8 more hex digits (38 digits in total).
+    ///
+    /// The checksum catches addresses mangled in copy-paste, including
+    /// dropped characters; read it back with
+    /// [`parse_checksummed`](Self::parse_checksummed).
+    pub fn to_checksummed(&self) -> String {
+        format!("{}{:08x}", self.to_hex(), crc32(&self.0))
+    }
+
+    /// Parses the output of [`to_checksummed`](Self::to_checksummed), or a
+    /// plain 30-digit hex address for compatibility.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAddressParseError::ChecksumMismatch`] if a checksum is
+    /// present but does not match, and the usual [`FromStr`] errors
+    /// otherwise.
+    pub fn parse_checksummed(s: &str) -> Result<Self, MidenAddressParseError> {
+        let hex_str = s.strip_prefix("0x").unwrap_or(s);
+        if !hex_str.is_ascii() || hex_str.len() != 2 * MIDEN_ACCOUNT_ID_BYTE_LEN + CHECKSUM_HEX_LEN
+        {
+            return s.parse();
+        }
+        let (address, checksum) = hex_str.split_at(2 * MIDEN_ACCOUNT_ID_BYTE_LEN);
+        let address: Self = address.parse()?;
+        let expected = format!("{:08x}", crc32(&address.0));
+        if !checksum.eq_ignore_ascii_case(&expected) {
+            return Err(MidenAddressParseError::ChecksumMismatch {
+                expected,
+                got: checksum.to_string(),
+            });
+        }
+        Ok(address)
+    }
+
+    /// Returns the bech32 address of this account on `network`
+    /// (e.g. `mtst1...` on testnet).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAddressParseError::UnknownNetwork`] if `network` has no
+    /// bech32 prefix.
+    pub fn to_bech32(
+        &self,
+        network: &MidenChainReference,
+    ) -> Result<String, MidenAddressParseError> {
+        let hrp = network
+            .bech32_hrp()
+            .ok_or_else(|| MidenAddressParseError::UnknownNetwork(network.to_string()))?;
+        let mut data = [0u8; MIDEN_ACCOUNT_ID_BYTE_LEN + 1];
+        data[0] = BECH32_ACCOUNT_ID_ADDRESS_TYPE;
+        data[1..].copy_from_slice(&self.0);
+        Ok(super::bech32::encode(hrp, &data))
+    }
+
+    /// Parses a bech32 address, returning the account and the network its
+    /// prefix belongs to.
+    ///
+    /// Routing parameters after a `_` separator are ignored. When `expected`
+    /// is set, an address for any other network is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checksum or encoding is invalid, the prefix is
+    /// not a known Miden network, the network differs from `expected`, or the
+    /// address does not hold an account ID.
+    pub fn from_bech32(
+        s: &str,
+        expected: Option<&MidenChainReference>,
+    ) -> Result<(Self, MidenChainReference), MidenAddressParseError> {
+        let (address, network) = Self::decode_bech32(s, expected)?;
+        address.validate()?;
+        Ok((address, network))
+    }
+
+    /// [`from_bech32`](Self::from_bech32) without [`validate`](Self::validate).
+    fn decode_bech32(
+        s: &str,
+        expected: Option<&MidenChainReference>,
+    ) -> Result<(Self, MidenChainReference), MidenAddressParseError> {
+        let account_part = s.split('_').next().unwrap_or(s);
+        let (hrp, data) = super::bech32::decode(account_part)
+            .map_err(|e| MidenAddressParseError::InvalidBech32(e.to_string()))?;
+        let network = MidenChainReference::from_bech32_hrp(&hrp)
+            .ok_or(MidenAddressParseError::UnknownNetwork(hrp))?;
+        if let Some(expected) = expected
+            && *expected != network
+        {
+            return Err(MidenAddressParseError::NetworkMismatch {
+                expected: expected.to_string(),
+                got: network.to_string(),
+            });
+        }
+        match data.split_first() {
+            Some((&BECH32_ACCOUNT_ID_ADDRESS_TYPE, id)) => {
+                Ok((Self::from_bytes_unchecked(id)?, network))
+            }
+            _ => Err(MidenAddressParseError::InvalidBech32(
+                "address does not hold an account ID".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the account type encoded in the ID.
+    pub fn account_type(&self) -> MidenAccountType {
+        match (self.0[ACCOUNT_ID_METADATA_BYTE] >> 4) & 0b11 {
+            0b00 => MidenAccountType::RegularAccountImmutableCode,
+            0b01 => MidenAccountType::RegularAccountUpdatableCode,
+            0b10 => MidenAccountType::FungibleFaucet,
+            _ => MidenAccountType::NonFungibleFaucet,
+        }
+    }
+
+    /// Returns `true` if the ID belongs to a fungible or non-fungible faucet.
+    pub fn is_faucet(&self) -> bool {
+        self.account_type().is_faucet()
+    }
+
+    /// Returns `true` if the ID belongs to a regular (non-faucet) account.
+    pub fn is_regular_account(&self) -> bool {
+        !self.is_faucet()
+    }
+
+    /// Returns the storage mode encoded in the ID, or `None` if the bits do
+    /// not name one (only possible for addresses built with the
+    /// `*_unchecked` constructors).
+    pub fn storage_mode(&self) -> Option<MidenStorageMode> {
+        match self.0[ACCOUNT_ID_METADATA_BYTE] >> 6 {
+            0b00 => Some(MidenStorageMode::Public),
+            0b01 => Some(MidenStorageMode::Network),
+            0b10 => Some(MidenStorageMode::Private),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `s` starts with the bech32 prefix of a known network.
+    fn looks_like_bech32(s: &str) -> bool {
+        let lower = s.to_ascii_lowercase();
+        MidenChainReference::BECH32_HRPS.iter().any(|(_, hrp)| {
+            lower
+                .strip_prefix(hrp)
+                .is_some_and(|rest| rest.starts_with('1'))
+        })
+    }
+}
+
+/// Number of hex digits appended by [`MidenAccountAddress::to_checksummed`].
+const CHECKSUM_HEX_LEN: usize = 8;
+
+/// CRC-32 (IEEE 802.3) of `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+const fn const_hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("Miden address contains a non-hex character"),
+    }
+}
+
+/// Builds a [`MidenAccountAddress`] from a hex string literal at compile
+/// time.
+///
+/// The literal must be 30 hex digits (15 bytes), with or without a `0x`
+/// prefix; anything else is a compile error rather than a runtime parse
+/// failure. Like [`MidenAccountAddress::from_hex_const`], the account ID
+/// layout is not checked.
+///
+/// # Example
+///
+/// ```
+/// use x402_chain_miden::chain::MidenAccountAddress;
+/// use x402_chain_miden::miden_address;
+///
+/// const FAUCET: MidenAccountAddress = miden_address!("0x37d5977a8e16d8205a360820f0230f");
+/// assert_eq!(FAUCET, "0x37d5977a8e16d8205a360820f0230f".parse().unwrap());
+/// ```
+///
+/// A literal of the wrong length does not compile:
+///
+/// ```compile_fail
+/// let _ = x402_chain_miden::miden_address!("0x37d5977a8e16d8205a360820f023");
+/// ```
+///
+/// Nor does one with a non-hex digit:
+///
+/// ```compile_fail
+/// let _ = x402_chain_miden::miden_address!("0x37d5977a8e16d8205a360820f0230g");
+/// ```
+#[macro_export]
+macro_rules! miden_address {
+    ($hex:literal) => {{
+        const ADDRESS: $crate::chain::MidenAccountAddress =
+            $crate::chain::MidenAccountAddress::from_hex_const($hex);
+        ADDRESS
+    }};
 }
 
 impl FromStr for MidenAccountAddress {
     type Err = MidenAddressParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.strip_prefix("0x").unwrap_or(s);
-        let bytes =
-            hex::decode(s).map_err(|e| MidenAddressParseError::InvalidHex(e.to_string()))?;
-        let arr: [u8; MIDEN_ACCOUNT_ID_BYTE_LEN] =
-            bytes
-                .try_into()
-                .map_err(|v: Vec<u8>| MidenAddressParseError::InvalidLength {
-                    expected: MIDEN_ACCOUNT_ID_BYTE_LEN,
-                    got: v.len(),
-                })?;
-        Ok(Self(arr))
+        let address = Self::parse_unchecked(s)?;
+        address.validate()?;
+        Ok(address)
     }
 }
 
@@ -123,12 +450,150 @@ impl MidenAccountAddress {
     }
 
     /// Creates a `MidenAccountAddress` from a miden-protocol `AccountId`.
+    ///
+    /// Infallible: the ID's `[u8; 15]` encoding has exactly
+    /// [`MIDEN_ACCOUNT_ID_BYTE_LEN`] bytes by construction.
     pub fn from_account_id(id: miden_protocol::account::AccountId) -> Self {
-        let hex_str = id.to_hex();
-        // to_hex returns "0x..." prefixed string
-        hex_str
-            .parse()
-            .expect("AccountId::to_hex always produces valid hex")
+        Self(id.into())
+    }
+
+    /// Creates a `MidenAccountAddress` from the hex form of a miden-protocol
+    /// `AccountId`, checking the result like [`FromStr`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `AccountId::to_hex` produces a string that does
+    /// not parse as a valid address.
+    pub fn try_from_account_id(
+        id: miden_protocol::account::AccountId,
+    ) -> Result<Self, MidenAddressParseError> {
+        id.to_hex().parse()
+    }
+
+    /// Returns the account ID as its `[prefix, suffix]` field elements.
+    ///
+    /// This is the order used by `AccountId`'s own `[Felt; 2]` conversions.
+    /// Note that P2ID note inputs store the pair the other way round:
+    ///
+    /// ```ignore
+    /// let [prefix, suffix] = pay_to.to_felts()?;
+    /// let inputs = NoteInputs::new(vec![suffix, prefix])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAddressParseError::InvalidAccountId`] if the address is
+    /// not a valid account ID (only possible for addresses built with
+    /// [`from_bytes_unchecked`](Self::from_bytes_unchecked)).
+    pub fn to_felts(&self) -> Result<[miden_protocol::Felt; 2], MidenAddressParseError> {
+        self.to_account_id().map(Into::into)
+    }
+
+    /// Creates an address from its `[prefix, suffix]` field elements, the
+    /// inverse of [`to_felts`](Self::to_felts).
+    ///
+    /// Recovering the target of a P2ID note, whose inputs are
+    /// `[suffix, prefix, ..]`:
+    ///
+    /// ```ignore
+    /// let inputs = note.recipient().inputs().values();
+    /// let target = MidenAccountAddress::from_felts([inputs[1], inputs[0]])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAddressParseError::InvalidAccountId`] if the elements
+    /// do not form a valid account ID.
+    pub fn from_felts(felts: [miden_protocol::Felt; 2]) -> Result<Self, MidenAddressParseError> {
+        miden_protocol::account::AccountId::try_from(felts)
+            .map(Self::from_account_id)
+            .map_err(|e| MidenAddressParseError::InvalidAccountId(e.to_string()))
+    }
+}
+
+#[cfg(feature = "miden-native")]
+impl From<miden_protocol::account::AccountId> for MidenAccountAddress {
+    fn from(id: miden_protocol::account::AccountId) -> Self {
+        Self::from_account_id(id)
+    }
+}
+
+#[cfg(feature = "miden-native")]
+impl TryFrom<&MidenAccountAddress> for miden_protocol::account::AccountId {
+    type Error = MidenAddressParseError;
+
+    fn try_from(address: &MidenAccountAddress) -> Result<Self, Self::Error> {
+        address.to_account_id()
+    }
+}
+
+#[cfg(feature = "miden-native")]
+impl TryFrom<MidenAccountAddress> for miden_protocol::account::AccountId {
+    type Error = MidenAddressParseError;
+
+    fn try_from(address: MidenAccountAddress) -> Result<Self, Self::Error> {
+        address.to_account_id()
+    }
+}
+
+/// The type of a Miden account, as encoded in its account ID.
+///
+/// Mirrors `miden_protocol::account::AccountType`, but is available without
+/// the `miden-native` feature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MidenAccountType {
+    /// A faucet issuing a fungible asset (e.g. a token).
+    FungibleFaucet,
+    /// A faucet issuing non-fungible assets.
+    NonFungibleFaucet,
+    /// A regular account whose code cannot be updated.
+    RegularAccountImmutableCode,
+    /// A regular account whose code can be updated (e.g. a wallet).
+    RegularAccountUpdatableCode,
+}
+
+impl MidenAccountType {
+    /// Returns `true` for both faucet types.
+    pub fn is_faucet(&self) -> bool {
+        matches!(self, Self::FungibleFaucet | Self::NonFungibleFaucet)
+    }
+}
+
+/// Where a Miden account's state is stored, as encoded in its account ID.
+///
+/// Mirrors `miden_protocol::account::AccountStorageMode`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MidenStorageMode {
+    /// The full account state is stored on-chain.
+    Public,
+    /// Public state, with transactions the network executes on the account's behalf.
+    Network,
+    /// Only a commitment to the account state is stored on-chain.
+    Private,
+}
+
+#[cfg(feature = "miden-native")]
+impl From<miden_protocol::account::AccountType> for MidenAccountType {
+    fn from(account_type: miden_protocol::account::AccountType) -> Self {
+        use miden_protocol::account::AccountType;
+        match account_type {
+            AccountType::FungibleFaucet => Self::FungibleFaucet,
+            AccountType::NonFungibleFaucet => Self::NonFungibleFaucet,
+            AccountType::RegularAccountImmutableCode => Self::RegularAccountImmutableCode,
+            AccountType::RegularAccountUpdatableCode => Self::RegularAccountUpdatableCode,
+        }
+    }
+}
+
+#[cfg(feature = "miden-native")]
+impl From<miden_protocol::account::AccountStorageMode> for MidenStorageMode {
+    fn from(storage_mode: miden_protocol::account::AccountStorageMode) -> Self {
+        use miden_protocol::account::AccountStorageMode;
+        match storage_mode {
+            AccountStorageMode::Public => Self::Public,
+            AccountStorageMode::Network => Self::Network,
+            AccountStorageMode::Private => Self::Private,
+        }
     }
 }
 
@@ -143,6 +608,38 @@ pub enum MidenAddressParseError {
     #[error("Invalid length: expected {expected} bytes, got {got}")]
     InvalidLength { expected: usize, got: usize },
 
+    /// The bech32 address is malformed (bad checksum, encoding or type).
+    #[error("Invalid bech32 address: {0}")]
+    InvalidBech32(String),
+
+    /// The bech32 prefix or network is not a known Miden network.
+    #[error("Unknown Miden network for bech32 address: {0}")]
+    UnknownNetwork(String),
+
+    /// The bech32 address belongs to a different network than expected.
+    #[error("Address is for network {got}, expected {expected}")]
+    NetworkMismatch { expected: String, got: String },
+
+    /// The ID uses an account ID version this crate does not know.
+    #[error("Unsupported account ID version: {0}")]
+    UnsupportedVersion(u8),
+
+    /// The storage mode bits do not name a storage mode.
+    #[error("Invalid account storage mode bits: {0:#04b}")]
+    InvalidStorageMode(u8),
+
+    /// The most significant bit of the ID suffix is set.
+    #[error("Invalid account ID suffix: most significant bit must be zero")]
+    InvalidSuffix,
+
+    /// The ID prefix is not a valid field element.
+    #[error("Invalid account ID prefix: not a valid field element")]
+    InvalidPrefix,
+
+    /// The checksum of a checksummed address does not match the address.
+    #[error("Address checksum mismatch: expected {expected}, got {got}")]
+    ChecksumMismatch { expected: String, got: String },
+
     /// The account ID is invalid (wrong length, checksum, etc.).
     #[cfg(feature = "miden-native")]
     #[error("Invalid account ID: {0}")]
@@ -150,108 +647,346 @@ pub enum MidenAddressParseError {
 }
 
 // ============================================================================
-// MidenChainReference
+// MidenTransactionId
 // ============================================================================
 
-/// A Miden chain reference (e.g., `testnet` or `mainnet`).
+/// Length of a Miden transaction ID in bytes (one `Word`).
+pub const MIDEN_TRANSACTION_ID_BYTE_LEN: usize = 32;
+
+/// A Miden transaction ID that serializes as a `0x`-prefixed hex string.
 ///
-/// Combined with the `miden` namespace, this forms a CAIP-2 chain ID
-/// like `miden:testnet` or `miden:mainnet`.
+/// Parsing accepts the ID with or without the `0x` prefix and in either
+/// case; the ID is stored as bytes, so differently written forms of the same
+/// ID compare and hash equal.
 ///
 /// # Example
 ///
 /// ```
-/// use x402_chain_miden::chain::MidenChainReference;
-/// use x402_types::chain::ChainId;
+/// use x402_chain_miden::chain::MidenTransactionId;
 ///
-/// let testnet = MidenChainReference::testnet();
-/// let chain_id: ChainId = testnet.into();
-/// assert_eq!(chain_id.to_string(), "miden:testnet");
+/// let hex = "ab".repeat(32);
+/// let bare: MidenTransactionId = hex.parse().unwrap();
+/// let prefixed: MidenTransactionId = format!("0x{}", hex.to_uppercase()).parse().unwrap();
+/// assert_eq!(bare, prefixed);
+/// assert_eq!(bare.to_string(), format!("0x{hex}"));
 /// ```
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub struct MidenChainReference(String);
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct MidenTransactionId([u8; MIDEN_TRANSACTION_ID_BYTE_LEN]);
 
-impl MidenChainReference {
-    /// Creates a new chain reference from a string.
-    pub fn new(reference: impl Into<String>) -> Self {
-        Self(reference.into())
+impl MidenTransactionId {
+    /// Creates a transaction ID from its raw bytes.
+    pub fn from_bytes(bytes: [u8; MIDEN_TRANSACTION_ID_BYTE_LEN]) -> Self {
+        Self(bytes)
     }
 
-    /// Returns the Miden testnet chain reference.
-    pub fn testnet() -> Self {
-        Self("testnet".to_string())
+    /// Returns the raw bytes of the transaction ID.
+    pub fn as_bytes(&self) -> &[u8; MIDEN_TRANSACTION_ID_BYTE_LEN] {
+        &self.0
     }
 
-    /// Returns the Miden mainnet chain reference.
-    pub fn mainnet() -> Self {
-        Self("mainnet".to_string())
+    /// Returns the `0x`-prefixed lowercase hex form.
+    pub fn to_hex(&self) -> String {
+        self.to_string()
     }
+}
 
-    /// Converts this chain reference to a CAIP-2 [`ChainId`].
-    pub fn as_chain_id(&self) -> ChainId {
-        ChainId::new(MIDEN_NAMESPACE, &self.0)
-    }
+impl FromStr for MidenTransactionId {
+    type Err = MidenTransactionIdParseError;
 
-    /// Returns the inner reference string.
-    pub fn inner(&self) -> &str {
-        &self.0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex_str = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let bytes = hex::decode(hex_str)
+            .map_err(|e| MidenTransactionIdParseError::InvalidHex(e.to_string()))?;
+        let got = bytes.len();
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| MidenTransactionIdParseError::InvalidLength {
+                expected: MIDEN_TRANSACTION_ID_BYTE_LEN,
+                got,
+            })
     }
 }
 
-impl Display for MidenChainReference {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+impl TryFrom<&str> for MidenTransactionId {
+    type Error = MidenTransactionIdParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
     }
 }
 
-impl From<MidenChainReference> for ChainId {
-    fn from(value: MidenChainReference) -> Self {
-        ChainId::new(MIDEN_NAMESPACE, value.0)
+impl TryFrom<String> for MidenTransactionId {
+    type Error = MidenTransactionIdParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
     }
 }
 
-impl From<&MidenChainReference> for ChainId {
-    fn from(value: &MidenChainReference) -> Self {
-        ChainId::new(MIDEN_NAMESPACE, &value.0)
+impl Display for MidenTransactionId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
     }
 }
 
-impl TryFrom<ChainId> for MidenChainReference {
-    type Error = MidenChainReferenceFormatError;
+impl Serialize for MidenTransactionId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
 
-    fn try_from(value: ChainId) -> Result<Self, Self::Error> {
-        if value.namespace != MIDEN_NAMESPACE {
-            return Err(MidenChainReferenceFormatError::InvalidNamespace(
-                value.namespace,
-            ));
-        }
-        Ok(MidenChainReference(value.reference))
+impl<'de> Deserialize<'de> for MidenTransactionId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
-impl TryFrom<&ChainId> for MidenChainReference {
-    type Error = MidenChainReferenceFormatError;
+/// Conversions to and from the miden-protocol `TransactionId` type.
+///
+/// These are only available when the `miden-native` feature is enabled.
+#[cfg(feature = "miden-native")]
+impl MidenTransactionId {
+    /// Converts this ID to a miden-protocol `TransactionId`.
+    pub fn to_transaction_id(
+        &self,
+    ) -> Result<miden_protocol::transaction::TransactionId, MidenTransactionIdParseError> {
+        use miden_protocol::utils::serde::Deserializable;
+        miden_protocol::transaction::TransactionId::read_from_bytes(&self.0)
+            .map_err(|e| MidenTransactionIdParseError::InvalidTransactionId(e.to_string()))
+    }
+}
 
-    fn try_from(value: &ChainId) -> Result<Self, Self::Error> {
-        if value.namespace != MIDEN_NAMESPACE {
-            return Err(MidenChainReferenceFormatError::InvalidNamespace(
-                value.namespace.clone(),
-            ));
-        }
-        Ok(MidenChainReference(value.reference.clone()))
+#[cfg(feature = "miden-native")]
+impl From<miden_protocol::transaction::TransactionId> for MidenTransactionId {
+    fn from(id: miden_protocol::transaction::TransactionId) -> Self {
+        // The same bytes `to_transaction_id` reads back.
+        Self(id.as_bytes())
     }
 }
 
-impl TryFrom<&str> for MidenChainReference {
-    type Error = MidenChainReferenceFormatError;
+/// Error returned when parsing a Miden transaction ID.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MidenTransactionIdParseError {
+    /// The hex string is invalid.
+    #[error("Invalid hex: {0}")]
+    InvalidHex(String),
+
+    /// The byte length is wrong (expected 32 bytes).
+    #[error("Invalid length: expected {expected} bytes, got {got}")]
+    InvalidLength { expected: usize, got: usize },
+
+    /// The bytes are not a valid transaction ID (e.g. a limb exceeds the
+    /// field modulus).
+    #[cfg(feature = "miden-native")]
+    #[error("Invalid transaction ID: {0}")]
+    InvalidTransactionId(String),
+}
+
+// ============================================================================
+// MidenChainReference
+// ============================================================================
+
+/// A Miden chain reference (e.g., `testnet` or `mainnet`).
+///
+/// Combined with the `miden` namespace, this forms a CAIP-2 chain ID
+/// like `miden:testnet` or `miden:mainnet`.
+///
+/// # Example
+///
+/// ```
+/// use x402_chain_miden::chain::MidenChainReference;
+/// use x402_types::chain::ChainId;
+///
+/// let testnet = MidenChainReference::testnet();
+/// let chain_id: ChainId = testnet.into();
+/// assert_eq!(chain_id.to_string(), "miden:testnet");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MidenChainReference(KnownOrCustom);
+
+/// The representation behind a [`MidenChainReference`].
+///
+/// Known networks are stored by variant, so they compare equal whatever case
+/// they were written in and always display in lowercase. Any other reference
+/// is kept verbatim.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum KnownOrCustom {
+    Testnet,
+    Mainnet,
+    Custom(String),
+}
+
+impl KnownOrCustom {
+    fn classify(reference: String) -> Self {
+        if reference.eq_ignore_ascii_case("testnet") {
+            Self::Testnet
+        } else if reference.eq_ignore_ascii_case("mainnet") {
+            Self::Mainnet
+        } else {
+            Self::Custom(reference)
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Testnet => "testnet",
+            Self::Mainnet => "mainnet",
+            Self::Custom(reference) => reference,
+        }
+    }
+}
+
+impl MidenChainReference {
+    /// Creates a new chain reference from a string, without checking that it
+    /// names a known network.
+    ///
+    /// Known network names are matched case-insensitively. Use the
+    /// `TryFrom<&str>` conversion to reject unknown networks.
+    pub fn new(reference: impl Into<String>) -> Self {
+        Self(KnownOrCustom::classify(reference.into()))
+    }
+
+    /// Returns the Miden testnet chain reference.
+    pub fn testnet() -> Self {
+        Self(KnownOrCustom::Testnet)
+    }
+
+    /// Returns the Miden mainnet chain reference.
+    pub fn mainnet() -> Self {
+        Self(KnownOrCustom::Mainnet)
+    }
+
+    /// Converts this chain reference to a CAIP-2 [`ChainId`].
+    pub fn as_chain_id(&self) -> ChainId {
+        ChainId::new(MIDEN_NAMESPACE, self.inner())
+    }
+
+    /// Returns the inner reference string.
+    pub fn inner(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns `true` if this is a known Miden network rather than a custom
+    /// reference.
+    pub fn is_known(&self) -> bool {
+        !matches!(self.0, KnownOrCustom::Custom(_))
+    }
+
+    /// Bech32 address prefixes (human-readable parts) of known networks.
+    #[cfg(not(feature = "custom-networks"))]
+    const BECH32_HRPS: &'static [(&'static str, &'static str)] =
+        &[("mainnet", "mm"), ("testnet", "mtst")];
+
+    /// Bech32 address prefixes (human-readable parts) of known networks.
+    ///
+    /// Devnet is a custom reference, so its prefix is only recognized when
+    /// custom references are accepted.
+    #[cfg(feature = "custom-networks")]
+    const BECH32_HRPS: &'static [(&'static str, &'static str)] =
+        &[("mainnet", "mm"), ("testnet", "mtst"), ("devnet", "mdev")];
+
+    /// Returns the bech32 address prefix of this network, if known.
+    pub fn bech32_hrp(&self) -> Option<&'static str> {
+        Self::BECH32_HRPS
+            .iter()
+            .find(|(reference, _)| *reference == self.inner())
+            .map(|(_, hrp)| *hrp)
+    }
+
+    /// Returns the network whose bech32 address prefix is `hrp`, if known.
+    pub fn from_bech32_hrp(hrp: &str) -> Option<Self> {
+        Self::BECH32_HRPS
+            .iter()
+            .find(|(_, known)| known.eq_ignore_ascii_case(hrp))
+            .map(|(reference, _)| Self::new(*reference))
+    }
+}
+
+impl Display for MidenChainReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner())
+    }
+}
+
+impl Serialize for MidenChainReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.inner())
+    }
+}
+
+/// Validates like the `TryFrom<&str>` conversion.
+impl<'de> Deserialize<'de> for MidenChainReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<MidenChainReference> for ChainId {
+    fn from(value: MidenChainReference) -> Self {
+        ChainId::new(MIDEN_NAMESPACE, value.inner())
+    }
+}
+
+impl From<&MidenChainReference> for ChainId {
+    fn from(value: &MidenChainReference) -> Self {
+        ChainId::new(MIDEN_NAMESPACE, value.inner())
+    }
+}
+
+/// Validates the reference like the `TryFrom<&str>` conversion.
+impl TryFrom<ChainId> for MidenChainReference {
+    type Error = MidenChainReferenceFormatError;
+
+    fn try_from(value: ChainId) -> Result<Self, Self::Error> {
+        MidenChainReference::try_from(&value)
+    }
+}
+
+/// Validates the reference like the `TryFrom<&str>` conversion.
+impl TryFrom<&ChainId> for MidenChainReference {
+    type Error = MidenChainReferenceFormatError;
+
+    fn try_from(value: &ChainId) -> Result<Self, Self::Error> {
+        if value.namespace != MIDEN_NAMESPACE {
+            return Err(MidenChainReferenceFormatError::InvalidNamespace(
+                value.namespace.clone(),
+            ));
+        }
+        MidenChainReference::try_from(value.reference.as_str())
+    }
+}
+
+/// Accepts the known networks in any case. Other references are rejected
+/// unless the `custom-networks` feature is enabled.
+impl TryFrom<&str> for MidenChainReference {
+    type Error = MidenChainReferenceFormatError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "testnet" | "mainnet" => Ok(MidenChainReference(value.to_string())),
-            _ => Err(MidenChainReferenceFormatError::InvalidReference(
+        let reference = MidenChainReference::new(value);
+        if value.is_empty() || (!reference.is_known() && !cfg!(feature = "custom-networks")) {
+            return Err(MidenChainReferenceFormatError::InvalidReference(
                 value.to_string(),
-            )),
+            ));
         }
+        Ok(reference)
     }
 }
 
@@ -261,8 +996,16 @@ pub enum MidenChainReferenceFormatError {
     /// The chain ID namespace is not `miden`.
     #[error("Invalid namespace {0}, expected miden")]
     InvalidNamespace(String),
-    /// The reference string is not a known Miden network.
-    #[error("Invalid reference {0}, expected testnet or mainnet")]
+    /// The reference string is not a known Miden network, or is empty when
+    /// custom networks are accepted.
+    #[cfg_attr(
+        not(feature = "custom-networks"),
+        error("Invalid reference {0}, expected testnet or mainnet")
+    )]
+    #[cfg_attr(
+        feature = "custom-networks",
+        error("Invalid reference {0:?}, expected a non-empty network name")
+    )]
     InvalidReference(String),
 }
 
@@ -275,7 +1018,12 @@ pub enum MidenChainReferenceFormatError {
 /// On Miden, tokens are issued by faucet accounts. The faucet's account ID
 /// serves as the token identifier (analogous to an ERC-20 contract address
 /// on EVM chains).
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+///
+/// The optional `symbol` and `name` are for display only: two deployments
+/// with the same chain, faucet and decimals are equal whatever their
+/// metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MidenTokenDeployment {
     /// The chain this faucet is deployed on.
     pub chain_reference: MidenChainReference,
@@ -283,10 +1031,41 @@ pub struct MidenTokenDeployment {
     pub faucet_id: MidenAccountAddress,
     /// Number of decimal places for the token (e.g., 6 for USDC-equivalent).
     pub decimals: u8,
+    /// Ticker symbol, e.g. `"USDC"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Human-readable token name, e.g. `"USD Coin"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl PartialEq for MidenTokenDeployment {
+    fn eq(&self, other: &Self) -> bool {
+        self.chain_reference == other.chain_reference
+            && self.faucet_id == other.faucet_id
+            && self.decimals == other.decimals
+    }
+}
+
+impl Eq for MidenTokenDeployment {}
+
+impl std::hash::Hash for MidenTokenDeployment {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.chain_reference.hash(state);
+        self.faucet_id.hash(state);
+        self.decimals.hash(state);
+    }
 }
 
 /// A token amount paired with its deployment information.
-#[derive(Debug, Clone)]
+///
+/// Displays in whole-token units, scaled by the token's decimals and
+/// followed by the token symbol if known (`1500000` with 6 decimals displays
+/// as `1.5 USDC`).
+///
+/// Amounts of different deployments are never equal and are not ordered
+/// (`partial_cmp` returns `None`); use the `checked_*` methods to combine them.
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct MidenDeployedTokenAmount {
     /// The amount in the token's smallest unit.
     pub amount: u64,
@@ -295,6 +1074,33 @@ pub struct MidenDeployedTokenAmount {
 }
 
 impl MidenTokenDeployment {
+    /// Creates a token deployment without symbol or name.
+    pub fn new(
+        chain_reference: MidenChainReference,
+        faucet_id: MidenAccountAddress,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            chain_reference,
+            faucet_id,
+            decimals,
+            symbol: None,
+            name: None,
+        }
+    }
+
+    /// Sets the ticker symbol shown next to amounts, e.g. `"USDC"`.
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Sets the human-readable token name, e.g. `"USD Coin"`.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Creates a token amount from a raw value.
     ///
     /// The value should already be in the token's smallest unit.
@@ -307,19 +1113,38 @@ impl MidenTokenDeployment {
 
     /// Parses a human-readable amount string into token units.
     ///
-    /// Accepts formats like `"10.50"`, `"1000"`, etc.
-    /// The amount is scaled by the token's decimal places.
+    /// Accepts formats like `"10.50"`, `"1000"`, `"1."` and `".5"`, with
+    /// surrounding whitespace ignored. The amount is scaled by the token's
+    /// decimal places.
     ///
     /// # Errors
     ///
     /// Returns an error if the input cannot be parsed or exceeds u64 range.
+    /// Signs, exponents and digit separators get their own error variants.
     pub fn parse(&self, v: &str) -> Result<MidenDeployedTokenAmount, MidenAmountParseError> {
-        let parts: Vec<&str> = v.split('.').collect();
-        let (whole, frac) = match parts.len() {
-            1 => (parts[0], ""),
-            2 => (parts[0], parts[1]),
-            _ => return Err(MidenAmountParseError::InvalidFormat(v.to_string())),
+        let v = v.trim();
+        if v.starts_with(['+', '-']) {
+            return Err(MidenAmountParseError::SignNotAllowed(v.to_string()));
+        }
+        if v.contains(['e', 'E']) {
+            return Err(MidenAmountParseError::ExponentNotAllowed(v.to_string()));
+        }
+        if let Some(found) = v.chars().find(|c| !c.is_ascii_digit() && *c != '.') {
+            return Err(MidenAmountParseError::InvalidCharacter {
+                input: v.to_string(),
+                found,
+            });
+        }
+        let (whole, frac) = match v.split_once('.') {
+            None => (v, ""),
+            Some((_, frac)) if frac.contains('.') => {
+                return Err(MidenAmountParseError::InvalidFormat(v.to_string()));
+            }
+            Some(parts) => parts,
         };
+        if whole.is_empty() && frac.is_empty() {
+            return Err(MidenAmountParseError::InvalidFormat(v.to_string()));
+        }
 
         let frac_len = frac.len() as u32;
         if frac_len > self.decimals as u32 {
@@ -329,18 +1154,18 @@ impl MidenTokenDeployment {
             });
         }
 
-        let whole_val: u64 = whole
-            .parse()
-            .map_err(|_| MidenAmountParseError::InvalidFormat(v.to_string()))?;
-        let frac_val: u64 = if frac.is_empty() {
-            0
-        } else {
-            frac.parse()
-                .map_err(|_| MidenAmountParseError::InvalidFormat(v.to_string()))?
+        // Only ASCII digits remain, so parsing can fail on overflow alone.
+        let parse_digits = |digits: &str| -> Result<u64, MidenAmountParseError> {
+            if digits.is_empty() {
+                return Ok(0);
+            }
+            digits.parse().map_err(|_| MidenAmountParseError::Overflow)
         };
+        let whole_val = parse_digits(whole)?;
+        let frac_val = parse_digits(frac)?;
 
-        let scale = 10u64.pow(self.decimals as u32);
-        let frac_scale = 10u64.pow(self.decimals as u32 - frac_len);
+        let scale = scale_factor(u32::from(self.decimals))?;
+        let frac_scale = scale_factor(u32::from(self.decimals) - frac_len)?;
 
         let total = whole_val
             .checked_mul(scale)
@@ -352,6 +1177,310 @@ impl MidenTokenDeployment {
             token: self.clone(),
         })
     }
+
+    /// Formats a base-unit amount in whole-token units.
+    ///
+    /// Shorthand for `self.amount(v).format_units()`; the inverse of
+    /// [`parse`](Self::parse).
+    pub fn from_base_units(&self, v: u64) -> String {
+        self.amount(v).format_units()
+    }
+
+    /// Returns an amount of `v` whole tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::Overflow`] if the amount in base units
+    /// exceeds u64 range.
+    pub fn from_major_units(
+        &self,
+        v: u64,
+    ) -> Result<MidenDeployedTokenAmount, MidenAmountParseError> {
+        let amount = v
+            .checked_mul(scale_factor(u32::from(self.decimals))?)
+            .ok_or(MidenAmountParseError::Overflow)?;
+        Ok(self.amount(amount))
+    }
+
+    /// Returns an amount of exactly one whole token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::Overflow`] if the token has so many
+    /// decimals that one token exceeds u64 range.
+    pub fn one(&self) -> Result<MidenDeployedTokenAmount, MidenAmountParseError> {
+        self.from_major_units(1)
+    }
+
+    /// Adds up amounts of this token, starting from zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::TokenMismatch`] if any amount is of a
+    /// different token, and [`MidenAmountParseError::Overflow`] if the total
+    /// exceeds u64 range.
+    pub fn checked_sum<'a>(
+        &self,
+        amounts: impl IntoIterator<Item = &'a MidenDeployedTokenAmount>,
+    ) -> Result<MidenDeployedTokenAmount, MidenAmountParseError> {
+        amounts
+            .into_iter()
+            .try_fold(self.amount(0), |total, amount| total.checked_add(amount))
+    }
+}
+
+impl MidenDeployedTokenAmount {
+    /// Expresses the same value in the units of another deployment.
+    ///
+    /// Only the decimals are taken into account; no exchange rate is applied,
+    /// so this is meant for the same asset issued with different precision.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::Overflow`] if scaling up exceeds u64
+    /// range, and [`MidenAmountParseError::PrecisionLoss`] if scaling down
+    /// would drop non-zero digits.
+    pub fn convert_to(
+        &self,
+        other: &MidenTokenDeployment,
+    ) -> Result<MidenDeployedTokenAmount, MidenAmountParseError> {
+        let from = self.token.decimals;
+        let to = other.decimals;
+        let amount = if to >= from {
+            self.amount
+                .checked_mul(scale_factor(u32::from(to - from))?)
+                .ok_or(MidenAmountParseError::Overflow)?
+        } else {
+            let precision_loss = MidenAmountParseError::PrecisionLoss {
+                amount: self.amount,
+                from,
+                to,
+            };
+            match scale_factor(u32::from(from - to)) {
+                Ok(divisor) if self.amount.is_multiple_of(divisor) => self.amount / divisor,
+                // Even the smallest non-zero amount is below one target unit.
+                Err(_) if self.amount == 0 => 0,
+                _ => return Err(precision_loss),
+            }
+        };
+        Ok(other.amount(amount))
+    }
+
+    /// Adds two amounts of the same token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::TokenMismatch`] if the tokens differ,
+    /// and [`MidenAmountParseError::Overflow`] if the sum exceeds u64 range.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, MidenAmountParseError> {
+        self.ensure_same_token(other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or(MidenAmountParseError::Overflow)?;
+        Ok(self.token.amount(amount))
+    }
+
+    /// Subtracts an amount of the same token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::TokenMismatch`] if the tokens differ,
+    /// and [`MidenAmountParseError::Underflow`] if `other` is larger.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, MidenAmountParseError> {
+        self.ensure_same_token(other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or(MidenAmountParseError::Underflow)?;
+        Ok(self.token.amount(amount))
+    }
+
+    /// Scales the amount by `numerator / denominator`, rounding down.
+    ///
+    /// The intermediate product is computed in u128, so e.g. a 2.5% fee
+    /// (`checked_mul_ratio(25, 1000)`) cannot overflow for any amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenAmountParseError::ZeroDenominator`] if `denominator` is
+    /// zero, and [`MidenAmountParseError::Overflow`] if the result exceeds u64
+    /// range.
+    pub fn checked_mul_ratio(
+        &self,
+        numerator: u64,
+        denominator: u64,
+    ) -> Result<Self, MidenAmountParseError> {
+        if denominator == 0 {
+            return Err(MidenAmountParseError::ZeroDenominator);
+        }
+        let scaled = u128::from(self.amount) * u128::from(numerator) / u128::from(denominator);
+        let amount = u64::try_from(scaled).map_err(|_| MidenAmountParseError::Overflow)?;
+        Ok(self.token.amount(amount))
+    }
+
+    fn ensure_same_token(&self, other: &Self) -> Result<(), MidenAmountParseError> {
+        if self.token != other.token {
+            return Err(MidenAmountParseError::TokenMismatch {
+                expected: format!("{} on {}", self.token.faucet_id, self.token.chain_reference),
+                got: format!(
+                    "{} on {}",
+                    other.token.faucet_id, other.token.chain_reference
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for MidenDeployedTokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self.token != other.token {
+            return None;
+        }
+        self.amount.partial_cmp(&other.amount)
+    }
+}
+
+/// Returns `10^decimals`, or [`MidenAmountParseError::Overflow`] if it does
+/// not fit in a u64.
+fn scale_factor(decimals: u32) -> Result<u64, MidenAmountParseError> {
+    10u64
+        .checked_pow(decimals)
+        .ok_or(MidenAmountParseError::Overflow)
+}
+
+impl MidenDeployedTokenAmount {
+    /// Formats the amount in whole-token units, e.g. `"1.5"` for `1500000`
+    /// with 6 decimals.
+    ///
+    /// Trailing zeros are trimmed, but a non-zero amount keeps at least one
+    /// fractional digit (`"1.0"`) when the token has decimals. The output
+    /// parses back to the same amount with [`MidenTokenDeployment::parse`].
+    pub fn format_units(&self) -> String {
+        let decimals = usize::from(self.token.decimals);
+        if decimals == 0 {
+            return self.amount.to_string();
+        }
+        if self.amount == 0 {
+            return "0".to_string();
+        }
+        let digits = format!("{:0>width$}", self.amount, width = decimals + 1);
+        let (whole, frac) = digits.split_at(digits.len() - decimals);
+        let frac = frac.trim_end_matches('0');
+        let frac = if frac.is_empty() { "0" } else { frac };
+        format!("{whole}.{frac}")
+    }
+
+    /// Converts the amount to whole-token units as an `f64`.
+    ///
+    /// Lossy: amounts above 2^53 base units, and most fractional values, are
+    /// rounded. Use it for display or estimates only, never for payment
+    /// amounts.
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(i32::from(self.token.decimals))
+    }
+}
+
+impl Display for MidenDeployedTokenAmount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.token.symbol {
+            Some(symbol) => write!(f, "{} {symbol}", self.format_units()),
+            None => f.write_str(&self.format_units()),
+        }
+    }
+}
+
+/// Wire shape of [`MidenDeployedTokenAmount`]: the token fields, flattened,
+/// plus the amount.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeployedTokenAmountRef<'a> {
+    #[serde(flatten)]
+    token: &'a MidenTokenDeployment,
+    amount: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeployedTokenAmountRepr {
+    #[serde(flatten)]
+    token: MidenTokenDeployment,
+    amount: Option<AmountRepr>,
+    amount_decimal: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AmountRepr {
+    Units(u64),
+    Text(String),
+}
+
+/// Serializes as the token deployment's fields plus `amount`, a string of
+/// base units:
+///
+/// ```json
+/// {
+///   "chainReference": "testnet",
+///   "faucetId": "0x37d5977a8e16d8205a360820f0230f",
+///   "decimals": 6,
+///   "symbol": "USDC",
+///   "amount": "1500000"
+/// }
+/// ```
+///
+/// The amount is a string so that values above 2^53 survive JSON parsers
+/// that read numbers as `f64`.
+impl Serialize for MidenDeployedTokenAmount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DeployedTokenAmountRef {
+            token: &self.token,
+            amount: self.amount.to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Accepts exactly one of:
+///
+/// - `amount`: base units, as a JSON integer or digit-only string
+///   (`1500000`, `"1500000"`);
+/// - `amountDecimal`: whole tokens, as a decimal string (`"1.5"`), parsed
+///   with [`MidenTokenDeployment::parse`].
+///
+/// The fields are separate so that a trailing `.0` cannot turn a price in
+/// base units into one in whole tokens.
+impl<'de> Deserialize<'de> for MidenDeployedTokenAmount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = DeployedTokenAmountRepr::deserialize(deserializer)?;
+        match (repr.amount, repr.amount_decimal) {
+            (Some(AmountRepr::Units(amount)), None) => Ok(repr.token.amount(amount)),
+            (Some(AmountRepr::Text(text)), None) => {
+                if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(serde::de::Error::custom(format!(
+                        "Invalid base-unit amount {text:?}, expected digits; \
+                         give whole tokens as amountDecimal"
+                    )));
+                }
+                let amount = text
+                    .parse::<u64>()
+                    .map_err(|_| serde::de::Error::custom(MidenAmountParseError::Overflow))?;
+                Ok(repr.token.amount(amount))
+            }
+            (None, Some(text)) => repr.token.parse(&text).map_err(serde::de::Error::custom),
+            (Some(_), Some(_)) => Err(serde::de::Error::custom(
+                "amount and amountDecimal are mutually exclusive",
+            )),
+            (None, None) => Err(serde::de::Error::missing_field("amount")),
+        }
+    }
 }
 
 /// Error returned when parsing a token amount.
@@ -360,12 +1489,34 @@ pub enum MidenAmountParseError {
     /// The input string is not a valid number.
     #[error("Invalid amount format: {0}")]
     InvalidFormat(String),
+    /// The input has a leading `+` or `-`.
+    #[error("Amounts must not be signed: {0}")]
+    SignNotAllowed(String),
+    /// The input uses exponent notation such as `1e6`.
+    #[error("Exponent notation is not supported, write the amount out in full: {0}")]
+    ExponentNotAllowed(String),
+    /// The input contains something other than digits and one `.`, such as a
+    /// `_` or `,` digit separator.
+    #[error("Invalid character {found:?} in amount {input:?}")]
+    InvalidCharacter { input: String, found: char },
     /// Too many decimal places for the token.
     #[error("Too many decimal places: got {got}, max {max}")]
     TooManyDecimals { got: u32, max: u8 },
     /// The resulting amount overflows u64.
     #[error("Amount overflow")]
     Overflow,
+    /// Converting to fewer decimals would drop non-zero digits.
+    #[error("Cannot convert {amount} from {from} to {to} decimals without losing precision")]
+    PrecisionLoss { amount: u64, from: u8, to: u8 },
+    /// The result would be negative.
+    #[error("Amount underflow")]
+    Underflow,
+    /// A ratio was given with a zero denominator.
+    #[error("Ratio denominator must not be zero")]
+    ZeroDenominator,
+    /// The amounts refer to different token deployments.
+    #[error("Token mismatch: expected {expected}, got {got}")]
+    TokenMismatch { expected: String, got: String },
 }
 
 // ============================================================================
@@ -392,6 +1543,89 @@ mod tests {
         assert_eq!(reference.inner(), "mainnet");
     }
 
+    #[test]
+    fn test_chain_reference_known_names_ignore_case() {
+        for name in ["testnet", "Testnet", "TESTNET"] {
+            let reference = MidenChainReference::try_from(name).unwrap();
+            assert_eq!(reference, MidenChainReference::testnet());
+            assert_eq!(reference.inner(), "testnet");
+        }
+        assert_eq!(
+            MidenChainReference::new("MainNet"),
+            MidenChainReference::mainnet()
+        );
+        let set: std::collections::HashSet<_> = [
+            MidenChainReference::new("Testnet"),
+            MidenChainReference::testnet(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 1);
+
+        // Custom references stay case-sensitive.
+        assert!(!MidenChainReference::new("devnet").is_known());
+        assert_ne!(
+            MidenChainReference::new("devnet"),
+            MidenChainReference::new("Devnet")
+        );
+    }
+
+    #[test]
+    fn test_chain_reference_serde() {
+        let json = serde_json::to_string(&MidenChainReference::new("Testnet")).unwrap();
+        assert_eq!(json, "\"testnet\"");
+
+        for (input, expected) in [
+            ("\"testnet\"", MidenChainReference::testnet()),
+            ("\"Testnet\"", MidenChainReference::testnet()),
+            ("\"MAINNET\"", MidenChainReference::mainnet()),
+        ] {
+            let reference: MidenChainReference = serde_json::from_str(input).unwrap();
+            assert_eq!(reference, expected, "{input}");
+        }
+
+        let custom = serde_json::from_str::<MidenChainReference>("\"goerli\"");
+        if cfg!(feature = "custom-networks") {
+            let custom = custom.unwrap();
+            assert_eq!(custom.inner(), "goerli");
+            assert_eq!(serde_json::to_string(&custom).unwrap(), "\"goerli\"");
+        } else {
+            let err = custom.unwrap_err().to_string();
+            assert!(err.contains("goerli"), "{err}");
+        }
+
+        assert!(serde_json::from_str::<MidenChainReference>("\"\"").is_err());
+        assert!(serde_json::from_str::<MidenChainReference>("1").is_err());
+    }
+
+    #[test]
+    fn test_chain_id_reference_is_validated() {
+        let goerli = ChainId::new("miden", "goerli");
+        let by_ref = MidenChainReference::try_from(&goerli);
+        let by_value = MidenChainReference::try_from(goerli);
+        if cfg!(feature = "custom-networks") {
+            assert_eq!(by_ref.unwrap().inner(), "goerli");
+            assert_eq!(by_value.unwrap().inner(), "goerli");
+        } else {
+            for result in [by_ref, by_value] {
+                let err = result.unwrap_err().to_string();
+                assert_eq!(err, "Invalid reference goerli, expected testnet or mainnet");
+            }
+        }
+
+        let err = MidenChainReference::try_from(ChainId::new("miden", ""))
+            .unwrap_err()
+            .to_string();
+        if cfg!(feature = "custom-networks") {
+            assert_eq!(
+                err,
+                "Invalid reference \"\", expected a non-empty network name"
+            );
+        } else {
+            assert_eq!(err, "Invalid reference , expected testnet or mainnet");
+        }
+    }
+
     #[test]
     fn test_chain_id_wrong_namespace() {
         let chain_id = ChainId::new("eip155", "8453");
@@ -401,15 +1635,103 @@ mod tests {
 
     #[test]
     fn test_miden_address_roundtrip() {
-        let hex_str = "0xabcdef1234567890abcdef12345678"; // 15 bytes
+        let hex_str = "0xabcdef12345678902bcdef12345678"; // 15 bytes
         let addr: MidenAccountAddress = hex_str.parse().unwrap();
         assert_eq!(addr.to_string(), hex_str);
     }
 
     #[test]
     fn test_miden_address_without_prefix() {
-        let addr: MidenAccountAddress = "abcdef1234567890abcdef12345678".parse().unwrap();
-        assert_eq!(addr.to_string(), "0xabcdef1234567890abcdef12345678");
+        let addr: MidenAccountAddress = "abcdef12345678902bcdef12345678".parse().unwrap();
+        assert_eq!(addr.to_string(), "0xabcdef12345678902bcdef12345678");
+    }
+
+    #[test]
+    fn test_miden_address_short() {
+        let addr: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        assert_eq!(addr.short(), "0x37d5…230f");
+    }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_miden_address_checksum_roundtrip() {
+        let addr: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        let checksummed = addr.to_checksummed();
+        assert_eq!(checksummed.len(), 2 + 38);
+        assert!(checksummed.starts_with(&addr.to_hex()));
+        assert_eq!(
+            MidenAccountAddress::parse_checksummed(&checksummed).unwrap(),
+            addr
+        );
+        assert_eq!(
+            MidenAccountAddress::parse_checksummed(&checksummed.to_uppercase()[2..]).unwrap(),
+            addr
+        );
+
+        // Plain hex stays accepted.
+        assert_eq!(
+            MidenAccountAddress::parse_checksummed("0x37d5977a8e16d8205a360820f0230f").unwrap(),
+            addr
+        );
+    }
+
+    #[test]
+    fn test_miden_address_checksum_detects_corruption() {
+        let addr: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        let checksummed = addr.to_checksummed();
+
+        // Every single flipped digit is caught (or fails address validation).
+        for position in 2..checksummed.len() {
+            let mut corrupted = checksummed.clone().into_bytes();
+            corrupted[position] = if corrupted[position] == b'0' {
+                b'1'
+            } else {
+                b'0'
+            };
+            let corrupted = String::from_utf8(corrupted).unwrap();
+            assert!(
+                MidenAccountAddress::parse_checksummed(&corrupted).is_err(),
+                "{corrupted}"
+            );
+        }
+
+        // Truncation is rejected rather than parsed as something else.
+        let truncated = &checksummed[..checksummed.len() - 1];
+        assert!(MidenAccountAddress::parse_checksummed(truncated).is_err());
+
+        let mut flipped = checksummed.clone();
+        flipped.replace_range(4..5, "0");
+        assert!(matches!(
+            MidenAccountAddress::parse_checksummed(&flipped),
+            Err(MidenAddressParseError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_miden_address_macro_matches_runtime_parse() {
+        const PREFIXED: MidenAccountAddress =
+            crate::miden_address!("0xabcdef12345678902bcdef12345678");
+        const BARE: MidenAccountAddress = crate::miden_address!("abcdef12345678902bcdef12345678");
+        const UPPER: MidenAccountAddress =
+            crate::miden_address!("0xABCDEF12345678902BCDEF12345678");
+        let parsed: MidenAccountAddress = "0xabcdef12345678902bcdef12345678".parse().unwrap();
+        assert_eq!(PREFIXED, parsed);
+        assert_eq!(BARE, parsed);
+        assert_eq!(UPPER, parsed);
+        assert_eq!(
+            MidenAccountAddress::from_hex_const("0xabcdef12345678902bcdef12345678"),
+            parsed
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "non-hex character")]
+    fn test_from_hex_const_panics_at_runtime_on_bad_hex() {
+        MidenAccountAddress::from_hex_const("0xabcdef12345678902bcdef1234567z");
     }
 
     #[test]
@@ -426,65 +1748,754 @@ mod tests {
 
     #[test]
     fn test_token_deployment_amount() {
-        let deployment = MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: "0xaabbccddeeff00112233aabbccddee".parse().unwrap(),
-            decimals: 6,
-        };
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0xaabbccddeeff00102233aabbccddee".parse().unwrap(),
+            6,
+        );
         let amount = deployment.amount(1_000_000);
         assert_eq!(amount.amount, 1_000_000);
     }
 
     #[test]
     fn test_token_deployment_parse_whole() {
-        let deployment = MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: "0xaabbccddeeff00112233aabbccddee".parse().unwrap(),
-            decimals: 6,
-        };
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0xaabbccddeeff00102233aabbccddee".parse().unwrap(),
+            6,
+        );
         let amount = deployment.parse("100").unwrap();
         assert_eq!(amount.amount, 100_000_000);
     }
 
     #[test]
     fn test_token_deployment_parse_with_decimals() {
-        let deployment = MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: "0xaabbccddeeff00112233aabbccddee".parse().unwrap(),
-            decimals: 6,
-        };
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0xaabbccddeeff00102233aabbccddee".parse().unwrap(),
+            6,
+        );
         let amount = deployment.parse("1.50").unwrap();
         assert_eq!(amount.amount, 1_500_000);
     }
 
     #[test]
     fn test_token_deployment_parse_too_many_decimals() {
-        let deployment = MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: "0xaabbccddeeff00112233aabbccddee".parse().unwrap(),
-            decimals: 2,
-        };
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0xaabbccddeeff00102233aabbccddee".parse().unwrap(),
+            2,
+        );
         let result = deployment.parse("1.234");
         assert!(result.is_err());
     }
 
     #[test]
     fn test_token_deployment_parse_smallest_unit() {
-        let deployment = MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: "0xaabbccddeeff00112233aabbccddee".parse().unwrap(),
-            decimals: 6,
-        };
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0xaabbccddeeff00102233aabbccddee".parse().unwrap(),
+            6,
+        );
         let amount = deployment.parse("0.000001").unwrap();
         assert_eq!(amount.amount, 1);
     }
 
+    #[test]
+    fn test_token_amount_display() {
+        let deployment = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0x37d5977a8e16d8205a360820f0230f".parse().unwrap(),
+            6,
+        );
+        assert_eq!(deployment.amount(1_500_000).to_string(), "1.5");
+        assert_eq!(deployment.amount(1_000_000).to_string(), "1.0");
+        assert_eq!(deployment.amount(1_234_567).to_string(), "1.234567");
+        assert_eq!(deployment.amount(1).to_string(), "0.000001");
+        assert_eq!(deployment.amount(0).to_string(), "0");
+        assert_eq!(
+            deployment.amount(u64::MAX).format_units(),
+            "18446744073709.551615"
+        );
+
+        let whole_only = MidenTokenDeployment {
+            decimals: 0,
+            ..deployment.clone()
+        };
+        assert_eq!(whole_only.amount(42).to_string(), "42");
+
+        let many_decimals = MidenTokenDeployment {
+            decimals: 30,
+            ..deployment
+        };
+        assert_eq!(
+            many_decimals.amount(5).format_units(),
+            "0.000000000000000000000000000005"
+        );
+    }
+
+    #[test]
+    fn test_token_amount_display_with_symbol() {
+        let usdc = deployment_with_decimals(6)
+            .with_symbol("USDC")
+            .with_name("USD Coin");
+        assert_eq!(usdc.amount(1_500_000).to_string(), "1.5 USDC");
+        assert_eq!(usdc.amount(1_500_000).format_units(), "1.5");
+        assert_eq!(usdc.amount(0).to_string(), "0 USDC");
+    }
+
+    #[test]
+    fn test_token_deployment_metadata_serde() {
+        let bare = deployment_with_decimals(6);
+        let json = serde_json::to_value(&bare).unwrap();
+        assert_eq!(json["decimals"], 6);
+        assert_eq!(json["chainReference"], "testnet");
+        assert!(json.get("symbol").is_none());
+        assert!(json.get("name").is_none());
+        let decoded: MidenTokenDeployment = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.symbol, None);
+
+        let usdc = bare.clone().with_symbol("USDC").with_name("USD Coin");
+        let json = serde_json::to_string(&usdc).unwrap();
+        let decoded: MidenTokenDeployment = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.symbol.as_deref(), Some("USDC"));
+        assert_eq!(decoded.name.as_deref(), Some("USD Coin"));
+
+        // Metadata does not affect identity.
+        assert_eq!(decoded, bare);
+    }
+
+    #[test]
+    fn test_token_amount_to_f64_lossy() {
+        let usdc = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0x37d5977a8e16d8205a360820f0230f".parse().unwrap(),
+            6,
+        );
+        assert_eq!(usdc.amount(1_500_000).to_f64_lossy(), 1.5);
+        assert_eq!(usdc.amount(0).to_f64_lossy(), 0.0);
+        assert!(usdc.amount(u64::MAX).to_f64_lossy() > 1.8e13);
+    }
+
+    #[test]
+    fn test_token_deployment_parse_edge_cases() {
+        let usdc = deployment_with_decimals(6);
+        let ok_cases = [
+            ("1", 1_000_000),
+            ("1.", 1_000_000),
+            (".5", 500_000),
+            ("0.5", 500_000),
+            ("0", 0),
+            ("0.000000", 0),
+            ("000.000001", 1),
+            (" 1 ", 1_000_000),
+            ("\t2.25\n", 2_250_000),
+            ("18446744073709.551615", u64::MAX),
+        ];
+        for (input, expected) in ok_cases {
+            let parsed = usdc
+                .parse(input)
+                .unwrap_or_else(|e| panic!("{input:?} should parse: {e}"));
+            assert_eq!(parsed.amount, expected, "{input:?}");
+        }
+
+        type Check = fn(&MidenAmountParseError) -> bool;
+        let err_cases: [(&str, Check); 14] = [
+            ("", |e| matches!(e, MidenAmountParseError::InvalidFormat(_))),
+            ("   ", |e| {
+                matches!(e, MidenAmountParseError::InvalidFormat(_))
+            }),
+            (".", |e| {
+                matches!(e, MidenAmountParseError::InvalidFormat(_))
+            }),
+            ("1.2.3", |e| {
+                matches!(e, MidenAmountParseError::InvalidFormat(_))
+            }),
+            ("+1", |e| {
+                matches!(e, MidenAmountParseError::SignNotAllowed(_))
+            }),
+            ("-1", |e| {
+                matches!(e, MidenAmountParseError::SignNotAllowed(_))
+            }),
+            ("1e6", |e| {
+                matches!(e, MidenAmountParseError::ExponentNotAllowed(_))
+            }),
+            ("1E6", |e| {
+                matches!(e, MidenAmountParseError::ExponentNotAllowed(_))
+            }),
+            ("1_000", |e| {
+                matches!(
+                    e,
+                    MidenAmountParseError::InvalidCharacter { found: '_', .. }
+                )
+            }),
+            ("1,000", |e| {
+                matches!(
+                    e,
+                    MidenAmountParseError::InvalidCharacter { found: ',', .. }
+                )
+            }),
+            ("1 000", |e| {
+                matches!(
+                    e,
+                    MidenAmountParseError::InvalidCharacter { found: ' ', .. }
+                )
+            }),
+            ("0.0000001", |e| {
+                matches!(e, MidenAmountParseError::TooManyDecimals { got: 7, max: 6 })
+            }),
+            ("18446744073709.551616", |e| {
+                matches!(e, MidenAmountParseError::Overflow)
+            }),
+            ("99999999999999999999999", |e| {
+                matches!(e, MidenAmountParseError::Overflow)
+            }),
+        ];
+        for (input, check) in err_cases {
+            let err = usdc.parse(input).expect_err(input);
+            assert!(check(&err), "{input:?} gave {err:?}");
+        }
+    }
+
+    #[test]
+    fn test_token_amount_format_units_roundtrip() {
+        use rand::{Rng, SeedableRng};
+
+        // Seeded, so a failing amount reproduces on every run.
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x006d_6964_656e);
+        for decimals in 0u8..=18 {
+            let deployment = MidenTokenDeployment::new(
+                MidenChainReference::testnet(),
+                "0x37d5977a8e16d8205a360820f0230f".parse().unwrap(),
+                decimals,
+            );
+            let mut amounts = vec![0, 1, 10, u64::MAX];
+            for _ in 0..200 {
+                // Vary the magnitude so small amounts are covered too.
+                amounts.push(rng.random::<u64>() >> rng.random_range(0..64));
+            }
+            for amount in amounts {
+                let formatted = deployment.amount(amount).format_units();
+                let parsed = deployment.parse(&formatted).unwrap();
+                assert_eq!(
+                    parsed.amount, amount,
+                    "{formatted} with {decimals} decimals"
+                );
+            }
+        }
+    }
+
+    fn deployment_with_decimals(decimals: u8) -> MidenTokenDeployment {
+        MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            "0x37d5977a8e16d8205a360820f0230f".parse().unwrap(),
+            decimals,
+        )
+    }
+
+    #[test]
+    fn test_token_deployment_major_units() {
+        let usdc = deployment_with_decimals(6);
+        assert_eq!(usdc.one().unwrap().amount, 1_000_000);
+        assert_eq!(usdc.from_major_units(25).unwrap().amount, 25_000_000);
+        assert_eq!(usdc.from_major_units(0).unwrap().amount, 0);
+        assert!(matches!(
+            usdc.from_major_units(u64::MAX),
+            Err(MidenAmountParseError::Overflow)
+        ));
+        assert_eq!(usdc.from_base_units(2_500_000), "2.5");
+
+        assert_eq!(deployment_with_decimals(0).one().unwrap().amount, 1);
+        assert_eq!(
+            deployment_with_decimals(19).one().unwrap().amount,
+            10_000_000_000_000_000_000
+        );
+        assert!(matches!(
+            deployment_with_decimals(20).one(),
+            Err(MidenAmountParseError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_token_deployment_parse_many_decimals_does_not_panic() {
+        let deployment = deployment_with_decimals(30);
+        assert!(matches!(
+            deployment.parse("1"),
+            Err(MidenAmountParseError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_token_amount_convert_to() {
+        let six = deployment_with_decimals(6);
+        let eight = deployment_with_decimals(8);
+        let zero = deployment_with_decimals(0);
+
+        // Scaling up is exact.
+        let converted = six.amount(1_500_000).convert_to(&eight).unwrap();
+        assert_eq!(converted.amount, 150_000_000);
+        assert_eq!(converted.token, eight);
+
+        // Scaling down is exact only when the dropped digits are zero.
+        assert_eq!(
+            eight.amount(150_000_000).convert_to(&six).unwrap().amount,
+            1_500_000
+        );
+        assert!(matches!(
+            eight.amount(150_000_001).convert_to(&six),
+            Err(MidenAmountParseError::PrecisionLoss {
+                amount: 150_000_001,
+                from: 8,
+                to: 6
+            })
+        ));
+        assert!(matches!(
+            six.amount(1).convert_to(&zero),
+            Err(MidenAmountParseError::PrecisionLoss { .. })
+        ));
+
+        // Same decimals is the identity.
+        assert_eq!(six.amount(42).convert_to(&six).unwrap().amount, 42);
+
+        // Scaling up past u64 overflows.
+        assert!(matches!(
+            six.amount(u64::MAX).convert_to(&eight),
+            Err(MidenAmountParseError::Overflow)
+        ));
+
+        // Differences too large for a u64 scale factor.
+        let thirty = deployment_with_decimals(30);
+        assert_eq!(thirty.amount(0).convert_to(&zero).unwrap().amount, 0);
+        assert!(matches!(
+            thirty.amount(1).convert_to(&zero),
+            Err(MidenAmountParseError::PrecisionLoss { .. })
+        ));
+        assert!(matches!(
+            zero.amount(1).convert_to(&thirty),
+            Err(MidenAmountParseError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_token_amount_checked_arithmetic() {
+        let usdc = deployment_with_decimals(6);
+        let a = usdc.amount(1_500_000);
+        let b = usdc.amount(500_000);
+
+        assert_eq!(a.checked_add(&b).unwrap(), usdc.amount(2_000_000));
+        assert_eq!(a.checked_sub(&b).unwrap(), usdc.amount(1_000_000));
+        assert!(matches!(
+            b.checked_sub(&a),
+            Err(MidenAmountParseError::Underflow)
+        ));
+        assert!(matches!(
+            usdc.amount(u64::MAX).checked_add(&usdc.amount(1)),
+            Err(MidenAmountParseError::Overflow)
+        ));
+
+        // 2.5% fee, rounded down.
+        assert_eq!(a.checked_mul_ratio(25, 1000).unwrap().amount, 37_500);
+        assert_eq!(usdc.amount(39).checked_mul_ratio(1, 2).unwrap().amount, 19);
+        assert_eq!(
+            usdc.amount(u64::MAX)
+                .checked_mul_ratio(3, 4)
+                .unwrap()
+                .amount,
+            u64::MAX / 4 * 3 + 2
+        );
+        assert!(matches!(
+            usdc.amount(u64::MAX).checked_mul_ratio(2, 1),
+            Err(MidenAmountParseError::Overflow)
+        ));
+        assert!(matches!(
+            a.checked_mul_ratio(1, 0),
+            Err(MidenAmountParseError::ZeroDenominator)
+        ));
+    }
+
+    #[test]
+    fn test_token_amount_token_mismatch() {
+        let usdc = deployment_with_decimals(6);
+        let other = MidenTokenDeployment {
+            chain_reference: MidenChainReference::mainnet(),
+            ..usdc.clone()
+        };
+        let a = usdc.amount(100);
+        let b = other.amount(100);
+
+        assert!(matches!(
+            a.checked_add(&b),
+            Err(MidenAmountParseError::TokenMismatch { .. })
+        ));
+        assert!(matches!(
+            a.checked_sub(&b),
+            Err(MidenAmountParseError::TokenMismatch { .. })
+        ));
+        assert_ne!(a, b);
+        assert_eq!(a.partial_cmp(&b), None);
+
+        assert!(usdc.amount(1) < usdc.amount(2));
+        assert!(usdc.amount(2) >= usdc.amount(2));
+    }
+
+    #[test]
+    fn test_token_deployment_checked_sum() {
+        let usdc = deployment_with_decimals(6);
+        let prices = [usdc.amount(1_000_000), usdc.amount(250_000), usdc.amount(1)];
+        assert_eq!(usdc.checked_sum(&prices).unwrap(), usdc.amount(1_250_001));
+        assert_eq!(usdc.checked_sum([]).unwrap(), usdc.amount(0));
+
+        let overflow = [usdc.amount(u64::MAX), usdc.amount(1)];
+        assert!(matches!(
+            usdc.checked_sum(&overflow),
+            Err(MidenAmountParseError::Overflow)
+        ));
+
+        let other = deployment_with_decimals(8);
+        assert!(matches!(
+            usdc.checked_sum(&[other.amount(1)]),
+            Err(MidenAmountParseError::TokenMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_miden_address_serde_roundtrip() {
-        let addr: MidenAccountAddress = "0xabcdef1234567890abcdef12345678".parse().unwrap();
+        let addr: MidenAccountAddress = "0xabcdef12345678902bcdef12345678".parse().unwrap();
         let json = serde_json::to_string(&addr).unwrap();
-        assert_eq!(json, "\"0xabcdef1234567890abcdef12345678\"");
+        assert_eq!(json, "\"0xabcdef12345678902bcdef12345678\"");
         let deserialized: MidenAccountAddress = serde_json::from_str(&json).unwrap();
         assert_eq!(addr, deserialized);
     }
+
+    #[test]
+    fn test_miden_address_rejects_malformed_ids() {
+        let cases = [
+            // Version nibble of byte 7 set to 1.
+            "0xaabbccddeeff00112233aabbccddee",
+            // Storage mode bits 0b11.
+            "0xaabbccddeeff00d02233aabbccddee",
+            // Suffix MSB set.
+            "0xaabbccddeeff00108233aabbccddee",
+            // Prefix above the field modulus.
+            "0xffffffffffffff102233aabbccddee",
+        ];
+        let results: Vec<_> = cases
+            .iter()
+            .map(|s| s.parse::<MidenAccountAddress>().unwrap_err())
+            .collect();
+        assert!(matches!(
+            results[0],
+            MidenAddressParseError::UnsupportedVersion(1)
+        ));
+        assert!(matches!(
+            results[1],
+            MidenAddressParseError::InvalidStorageMode(0b11)
+        ));
+        assert!(matches!(results[2], MidenAddressParseError::InvalidSuffix));
+        assert!(matches!(results[3], MidenAddressParseError::InvalidPrefix));
+
+        assert!(MidenAccountAddress::from_bytes(&[0xFF; 15]).is_err());
+        let json = format!("\"{}\"", cases[0]);
+        assert!(serde_json::from_str::<MidenAccountAddress>(&json).is_err());
+    }
+
+    #[test]
+    fn test_miden_address_unchecked_skips_validation() {
+        let addr =
+            MidenAccountAddress::parse_unchecked("0xaabbccddeeff00112233aabbccddee").unwrap();
+        assert!(addr.validate().is_err());
+        assert_eq!(addr.to_hex(), "0xaabbccddeeff00112233aabbccddee");
+
+        let addr = MidenAccountAddress::from_bytes_unchecked(&[0xFF; 15]).unwrap();
+        assert!(addr.validate().is_err());
+        assert!(matches!(
+            MidenAccountAddress::from_bytes_unchecked(&[0; 14]),
+            Err(MidenAddressParseError::InvalidLength { got: 14, .. })
+        ));
+    }
+
+    #[test]
+    fn test_miden_address_account_type_and_storage_mode() {
+        let faucet: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        assert_eq!(faucet.account_type(), MidenAccountType::FungibleFaucet);
+        assert!(faucet.is_faucet());
+        assert!(!faucet.is_regular_account());
+        assert_eq!(faucet.storage_mode(), Some(MidenStorageMode::Public));
+
+        let wallet: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        assert_eq!(
+            wallet.account_type(),
+            MidenAccountType::RegularAccountUpdatableCode
+        );
+        assert!(wallet.is_regular_account());
+
+        let cases = [
+            (
+                0x00,
+                MidenAccountType::RegularAccountImmutableCode,
+                MidenStorageMode::Public,
+            ),
+            (
+                0x50,
+                MidenAccountType::RegularAccountUpdatableCode,
+                MidenStorageMode::Network,
+            ),
+            (
+                0xa0,
+                MidenAccountType::FungibleFaucet,
+                MidenStorageMode::Private,
+            ),
+            (
+                0x30,
+                MidenAccountType::NonFungibleFaucet,
+                MidenStorageMode::Public,
+            ),
+        ];
+        for (metadata, account_type, storage_mode) in cases {
+            let mut bytes = [0x01; 15];
+            bytes[7] = metadata;
+            let addr = MidenAccountAddress::from_bytes(&bytes).unwrap();
+            assert_eq!(addr.account_type(), account_type);
+            assert_eq!(addr.storage_mode(), Some(storage_mode));
+        }
+
+        let mut bytes = [0x01; 15];
+        bytes[7] = 0xc0;
+        let addr = MidenAccountAddress::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(addr.storage_mode(), None);
+    }
+
+    #[test]
+    fn test_miden_address_bech32_roundtrip() {
+        let addr: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        let testnet = MidenChainReference::testnet();
+        let encoded = addr.to_bech32(&testnet).unwrap();
+        assert!(encoded.starts_with("mtst1"));
+
+        let (decoded, network) =
+            MidenAccountAddress::from_bech32(&encoded, Some(&testnet)).unwrap();
+        assert_eq!(decoded, addr);
+        assert_eq!(network, testnet);
+        assert_eq!(encoded.parse::<MidenAccountAddress>().unwrap(), addr);
+        assert_eq!(
+            encoded
+                .to_uppercase()
+                .parse::<MidenAccountAddress>()
+                .unwrap(),
+            addr
+        );
+        let with_routing = format!("{encoded}_qruqqypuyph");
+        assert_eq!(with_routing.parse::<MidenAccountAddress>().unwrap(), addr);
+
+        // Display stays hex.
+        assert_eq!(decoded.to_string(), "0xaabbccddeeff00102233aabbccddee");
+    }
+
+    #[test]
+    fn test_miden_address_bech32_network_prefixes() {
+        let addr = MidenAccountAddress::from_bytes(&[0x10; 15]).unwrap();
+        let mainnet = addr.to_bech32(&MidenChainReference::mainnet()).unwrap();
+        assert!(mainnet.starts_with("mm1"));
+        assert!(
+            addr.to_bech32(&MidenChainReference::new("custom"))
+                .is_err_and(|e| matches!(e, MidenAddressParseError::UnknownNetwork(_)))
+        );
+
+        let result =
+            MidenAccountAddress::from_bech32(&mainnet, Some(&MidenChainReference::testnet()));
+        assert!(matches!(
+            result,
+            Err(MidenAddressParseError::NetworkMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_devnet_bech32_prefix_requires_custom_networks() {
+        let devnet = MidenChainReference::from_bech32_hrp("mdev");
+        if cfg!(feature = "custom-networks") {
+            assert_eq!(devnet, Some(MidenChainReference::new("devnet")));
+        } else {
+            assert_eq!(devnet, None);
+            assert_eq!(MidenChainReference::new("devnet").bech32_hrp(), None);
+        }
+    }
+
+    #[test]
+    fn test_miden_address_bech32_rejects_invalid() {
+        let addr = MidenAccountAddress::from_bytes(&[0x10; 15]).unwrap();
+        let encoded = addr.to_bech32(&MidenChainReference::testnet()).unwrap();
+
+        // Corrupt the last checksum character.
+        let mut corrupted = encoded.clone();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(matches!(
+            corrupted.parse::<MidenAccountAddress>(),
+            Err(MidenAddressParseError::InvalidBech32(_))
+        ));
+
+        // Valid bech32m, but not a Miden network prefix.
+        let foreign = super::super::bech32::encode("bc", &[232; 16]);
+        assert!(matches!(
+            MidenAccountAddress::from_bech32(&foreign, None),
+            Err(MidenAddressParseError::UnknownNetwork(_))
+        ));
+
+        // Known prefix, but the payload is not an account ID address.
+        let other_type = super::super::bech32::encode("mtst", &[0; 16]);
+        assert!(matches!(
+            other_type.parse::<MidenAccountAddress>(),
+            Err(MidenAddressParseError::InvalidBech32(_))
+        ));
+    }
+
+    #[test]
+    fn test_miden_address_serde_accepts_bech32() {
+        let addr = MidenAccountAddress::from_bytes(&[0x20; 15]).unwrap();
+        let encoded = addr.to_bech32(&MidenChainReference::testnet()).unwrap();
+        let deserialized: MidenAccountAddress =
+            serde_json::from_str(&format!("\"{encoded}\"")).unwrap();
+        assert_eq!(deserialized, addr);
+    }
+
+    #[test]
+    fn test_deployed_amount_serde_roundtrip() {
+        let usdc = deployment_with_decimals(6).with_symbol("USDC");
+        let amount = usdc.amount(u64::MAX);
+        let json = serde_json::to_value(&amount).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chainReference": "testnet",
+                "faucetId": "0x37d5977a8e16d8205a360820f0230f",
+                "decimals": 6,
+                "symbol": "USDC",
+                "amount": u64::MAX.to_string(),
+            })
+        );
+        let decoded: MidenDeployedTokenAmount = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, amount);
+        assert_eq!(decoded.token.symbol.as_deref(), Some("USDC"));
+    }
+
+    #[test]
+    fn test_deployed_amount_deserializes_mixed_formats() {
+        let decode = |field: &str, amount: serde_json::Value| {
+            let mut json = serde_json::json!({
+                "chainReference": "testnet",
+                "faucetId": "0x37d5977a8e16d8205a360820f0230f",
+                "decimals": 6,
+            });
+            json[field] = amount;
+            serde_json::from_value::<MidenDeployedTokenAmount>(json)
+        };
+        // `amount` is base units, as an integer or a digit-only string.
+        assert_eq!(
+            decode("amount", serde_json::json!(1_500_000))
+                .unwrap()
+                .amount,
+            1_500_000
+        );
+        assert_eq!(
+            decode("amount", serde_json::json!("1500000"))
+                .unwrap()
+                .amount,
+            1_500_000
+        );
+        // `amountDecimal` is whole tokens, with or without a fraction.
+        assert_eq!(
+            decode("amountDecimal", serde_json::json!("1.5"))
+                .unwrap()
+                .amount,
+            1_500_000
+        );
+        assert_eq!(
+            decode("amountDecimal", serde_json::json!("1"))
+                .unwrap()
+                .amount,
+            1_000_000
+        );
+
+        // A decimal in `amount` is refused rather than read as whole tokens.
+        for invalid in [
+            serde_json::json!("1.0"),
+            serde_json::json!("-1"),
+            serde_json::json!(""),
+            serde_json::json!("1e6"),
+            serde_json::json!("99999999999999999999"),
+            serde_json::json!(-1),
+            serde_json::json!(1.5),
+        ] {
+            assert!(decode("amount", invalid.clone()).is_err(), "{invalid}");
+        }
+        for invalid in [
+            serde_json::json!("0.0000001"),
+            serde_json::json!("1e6"),
+            serde_json::json!(1.5),
+        ] {
+            assert!(
+                decode("amountDecimal", invalid.clone()).is_err(),
+                "{invalid}"
+            );
+        }
+
+        // Exactly one of the two fields must be given.
+        let mut both = serde_json::to_value(deployment_with_decimals(6).amount(1)).unwrap();
+        both["amountDecimal"] = serde_json::json!("1");
+        assert!(serde_json::from_value::<MidenDeployedTokenAmount>(both).is_err());
+        let mut neither = serde_json::to_value(deployment_with_decimals(6).amount(1)).unwrap();
+        neither.as_object_mut().unwrap().remove("amount");
+        assert!(serde_json::from_value::<MidenDeployedTokenAmount>(neither).is_err());
+    }
+
+    #[test]
+    fn test_transaction_id_normalization() {
+        let hex = "0123456789abcdef".repeat(4);
+        let forms = [
+            hex.clone(),
+            format!("0x{hex}"),
+            format!("0X{}", hex.to_uppercase()),
+            hex.to_uppercase(),
+        ];
+        let ids: Vec<MidenTransactionId> = forms.iter().map(|f| f.parse().unwrap()).collect();
+        assert!(ids.iter().all(|id| *id == ids[0]));
+        assert_eq!(ids[0].to_string(), format!("0x{hex}"));
+        assert_eq!(ids[0].to_hex(), format!("0x{hex}"));
+        assert_eq!(ids[0].as_bytes()[0], 0x01);
+
+        let set: std::collections::HashSet<_> = ids.into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(
+            MidenTransactionId::try_from(format!("0x{hex}")).unwrap(),
+            MidenTransactionId::from_bytes(*set.iter().next().unwrap().as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_transaction_id_rejects_invalid() {
+        assert!(matches!(
+            "0xzz".parse::<MidenTransactionId>(),
+            Err(MidenTransactionIdParseError::InvalidHex(_))
+        ));
+        assert_eq!(
+            "0xabcd".parse::<MidenTransactionId>(),
+            Err(MidenTransactionIdParseError::InvalidLength {
+                expected: 32,
+                got: 2
+            })
+        );
+        assert!(MidenTransactionId::try_from("").is_err());
+        assert!(MidenTransactionId::try_from("ab".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn test_transaction_id_serde() {
+        let id = MidenTransactionId::from_bytes([0xAB; 32]);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", "ab".repeat(32)));
+        let unprefixed: MidenTransactionId =
+            serde_json::from_str(&format!("\"{}\"", "AB".repeat(32))).unwrap();
+        assert_eq!(unprefixed, id);
+        assert!(serde_json::from_str::<MidenTransactionId>("\"0x01\"").is_err());
+    }
 }
@@ -22,6 +22,8 @@
 //! - `facilitator` - Facilitator-side chain provider and lightweight verification
 //! - `miden-native` - Miden protocol types using `miden-protocol`
 //! - `miden-client-native` - Full miden-client integration (includes `miden-native`)
+//! - `custom-networks` - Accept chain references other than `testnet` and `mainnet`
+//! - `test-utils` - Helpers for reproducible tests; never enable in production
 //!
 //! # Usage
 //!
@@ -68,6 +68,14 @@ const BACKGROUND_SYNC_INTERVAL_SECS: u64 = 15;
 /// facilitator processes.
 const MAX_CACHED_HEADERS: usize = 10_000;
 
+/// Default maximum decoded size (in bytes) of a hex-encoded payment header
+/// field such as `inclusion_proof` or `note_metadata`.
+///
+/// A `SparseMerklePath` for Miden's 16-level note tree is well under 1 KiB,
+/// so this leaves ample headroom while keeping hostile inputs from being
+/// decoded in full.
+pub const DEFAULT_MAX_HEADER_FIELD_BYTES: usize = 16 * 1024;
+
 /// Cached block header data needed for lightweight verification.
 ///
 /// This struct stores the subset of a Miden block header that the
@@ -135,6 +143,16 @@ pub struct FacilitatorChainState {
     ///
     /// Used to validate that block headers belong to the expected chain.
     chain_reference: MidenChainReference,
+
+    /// Maximum decoded size of a hex-encoded payment header field.
+    ///
+    /// Checked before decoding so oversized inputs are rejected cheaply.
+    max_header_field_bytes: usize,
+
+    /// Whether a note tag mismatch fails verification.
+    ///
+    /// When `false`, a mismatch is only logged (with the `tracing` feature).
+    enforce_note_tag: bool,
 }
 
 impl FacilitatorChainState {
@@ -152,9 +170,30 @@ impl FacilitatorChainState {
             block_headers: Arc::new(RwLock::new(HashMap::new())),
             rpc_url,
             chain_reference,
+            max_header_field_bytes: DEFAULT_MAX_HEADER_FIELD_BYTES,
+            enforce_note_tag: true,
         }
     }
 
+    /// Sets the maximum decoded size of a hex-encoded payment header field.
+    ///
+    /// Defaults to [`DEFAULT_MAX_HEADER_FIELD_BYTES`]. Verification rejects
+    /// larger fields with [`MidenExactError::PayloadTooLarge`].
+    pub fn with_max_header_field_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_header_field_bytes = max_bytes;
+        self
+    }
+
+    /// Sets whether a note tag mismatch fails verification.
+    ///
+    /// Enabled by default. Facilitators that do not care whether the
+    /// recipient's wallet can discover the note may downgrade the check to
+    /// a warning.
+    pub fn with_note_tag_enforcement(mut self, enforce: bool) -> Self {
+        self.enforce_note_tag = enforce;
+        self
+    }
+
     /// Gets a block header, using the cache first and falling back to RPC.
     ///
     /// # Cache Strategy
@@ -172,6 +211,10 @@ impl FacilitatorChainState {
     ///
     /// Without `miden-client-native`, the RPC fallback returns an error
     /// indicating that the block must be pre-cached.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self), fields(cache_hit = tracing::field::Empty))
+    )]
     pub async fn get_block_header(
         &self,
         block_num: u32,
@@ -184,10 +227,15 @@ impl FacilitatorChainState {
                 .map_err(|e| MidenExactError::ProviderError(format!("Cache lock poisoned: {e}")))?;
 
             if let Some(header) = cache.get(&block_num) {
+                #[cfg(feature = "tracing")]
+                tracing::Span::current().record("cache_hit", true);
                 return Ok(header.clone());
             }
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("cache_hit", false);
+
         // Cache miss: fetch from RPC
         let header = self.fetch_block_header_rpc(block_num).await?;
 
@@ -319,6 +367,16 @@ impl FacilitatorChainState {
         &self.chain_reference
     }
 
+    /// Returns the maximum decoded size of a hex-encoded payment header field.
+    pub fn max_header_field_bytes(&self) -> usize {
+        self.max_header_field_bytes
+    }
+
+    /// Returns `true` if a note tag mismatch fails verification.
+    pub fn enforce_note_tag(&self) -> bool {
+        self.enforce_note_tag
+    }
+
     /// Manually inserts a block header into the cache.
     ///
     /// Useful for testing and for pre-populating the cache without
@@ -371,10 +429,8 @@ impl FacilitatorChainState {
         use miden_protocol::block::BlockNumber;
 
         let endpoint =
-            self.rpc_url
-                .as_str()
-                .try_into()
-                .map_err(|e: miden_client::rpc::RpcEndpoint| {
+            miden_client::rpc::Endpoint::try_from(self.rpc_url.as_str())
+                .map_err(|e| {
                     MidenExactError::ProviderError(format!(
                         "Invalid RPC URL '{}': {e}",
                         self.rpc_url
@@ -426,10 +482,8 @@ impl FacilitatorChainState {
         use miden_client::rpc::{GrpcClient, NodeRpcClient};
 
         let endpoint =
-            self.rpc_url
-                .as_str()
-                .try_into()
-                .map_err(|e: miden_client::rpc::RpcEndpoint| {
+            miden_client::rpc::Endpoint::try_from(self.rpc_url.as_str())
+                .map_err(|e| {
                     MidenExactError::ProviderError(format!(
                         "Invalid RPC URL '{}': {e}",
                         self.rpc_url
@@ -446,7 +500,7 @@ impl FacilitatorChainState {
                 MidenExactError::ProviderError(format!("Failed to fetch latest block header: {e}"))
             })?;
 
-        let block_num = block_header.block_num().into();
+        let block_num = block_header.block_num().as_u32();
 
         Ok(CachedBlockHeader {
             block_num,
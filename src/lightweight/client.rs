@@ -17,7 +17,7 @@
 //! - Without `miden-client-native`: Trait definition only (no implementation),
 //!   allowing downstream crates to provide their own implementation.
 
-use super::types::{LightweightPaymentHeader, LightweightPaymentRequirement};
+use super::types::{LightweightPaymentHeader, LightweightPaymentRequirement, PaymentReceipt};
 
 /// Trait for lightweight payment creation (agent side).
 ///
@@ -38,6 +38,15 @@ pub trait LightweightPayerLike: Send + Sync {
     /// Returns the sender's account ID as a hex string.
     fn account_id(&self) -> String;
 
+    /// Returns the account ID that pays requirements on `network`, or `None`
+    /// if this payer cannot pay on it.
+    ///
+    /// The default implementation returns [`account_id`](Self::account_id)
+    /// for every network; payers routing by network override it.
+    fn account_id_for(&self, _network: &x402_types::chain::ChainId) -> Option<String> {
+        Some(self.account_id())
+    }
+
     /// Creates a P2ID payment, proves it, submits it to the network,
     /// and returns a lightweight payment header with the inclusion proof.
     ///
@@ -62,18 +71,575 @@ pub trait LightweightPayerLike: Send + Sync {
         &self,
         requirement: &LightweightPaymentRequirement,
     ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error>;
+
+    /// Same as [`create_and_submit_payment`](Self::create_and_submit_payment),
+    /// but also returns a [`PaymentReceipt`] the agent can persist.
+    ///
+    /// The default implementation builds the receipt from the requirement and
+    /// header, without a transaction ID.
+    async fn create_and_submit_payment_with_receipt(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<(LightweightPaymentHeader, PaymentReceipt), x402_types::scheme::client::X402Error>
+    {
+        let header = self.create_and_submit_payment(requirement).await?;
+        let payer = self
+            .account_id_for(&requirement.network)
+            .unwrap_or_else(|| self.account_id());
+        let receipt = PaymentReceipt::new(payer, requirement, &header, None);
+        Ok((header, receipt))
+    }
+}
+
+/// Why a lightweight payment could not be made.
+///
+/// Converted into [`X402Error::SigningError`](x402_types::scheme::client::X402Error)
+/// at the [`LightweightPayerLike`] boundary, since that type has no finer
+/// variants. Use [`is_retryable`](Self::is_retryable) to decide whether a
+/// failed payment is worth retrying; the message of retryable errors says so.
+#[cfg(feature = "client")]
+#[derive(Debug, thiserror::Error)]
+pub enum PayerError {
+    /// The payer's own account ID is invalid.
+    #[error("Invalid sender account ID: {0}")]
+    InvalidSender(String),
+
+    /// The server's `pay_to` account ID is invalid.
+    #[error("Invalid target account ID (pay_to): {0}")]
+    InvalidRecipient(String),
+
+    /// The requirement's faucet account ID is invalid.
+    #[error("Invalid faucet account ID: {0}")]
+    InvalidFaucet(String),
+
+    /// The payer has no account on the requirement's network.
+    #[error("No payer account configured for network {0}")]
+    UnsupportedNetwork(String),
+
+    /// The payment note cannot be built from the requirement.
+    #[error("Invalid payment requirement: {0}")]
+    InvalidRequirement(String),
+
+    /// Syncing the client with the network failed.
+    #[error("State sync failed (retryable): {source}")]
+    SyncFailed {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Executing, proving or submitting the payment transaction failed.
+    #[error("Transaction submission failed: {source}")]
+    ExecutionFailed {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Executing and proving took longer than the proving timeout.
+    ///
+    /// The transaction was not submitted, so retrying cannot pay twice.
+    #[error("Proving timed out after {}s (retryable)", .0.as_secs())]
+    Timeout(std::time::Duration),
+
+    /// Reading the payment note back from the client's store failed.
+    #[error("Failed to query output notes: {source}")]
+    Store {
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// The payment note is not yet committed to a block with an inclusion proof.
+    ///
+    /// Reported as the source of [`AwaitingInclusion`](Self::AwaitingInclusion).
+    #[error("{0}")]
+    NoteNotCommitted(String),
+
+    /// The payment transaction was submitted, but its note's inclusion proof
+    /// could not be obtained.
+    ///
+    /// A retry resumes this payment at the inclusion step instead of paying
+    /// again.
+    #[error("Transaction {transaction_id} submitted, awaiting inclusion (retryable): {source}")]
+    AwaitingInclusion {
+        transaction_id: crate::chain::MidenTransactionId,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[cfg(feature = "client")]
+impl PayerError {
+    /// Returns `true` if retrying the payment later may succeed.
+    ///
+    /// Invalid accounts or requirements will fail again, and a failed
+    /// execution usually means the account cannot make the payment (e.g.
+    /// insufficient balance), so those are not retryable. A payment that was
+    /// already submitted is resumed rather than paid again, so failing to
+    /// obtain its inclusion proof is retryable.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            PayerError::SyncFailed { .. }
+                | PayerError::Timeout(_)
+                | PayerError::AwaitingInclusion { .. }
+        )
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<PayerError> for x402_types::scheme::client::X402Error {
+    fn from(value: PayerError) -> Self {
+        x402_types::scheme::client::X402Error::SigningError(value.to_string())
+    }
+}
+
+/// When a payer syncs chain state before building a payment.
+///
+/// Paying from stale local state makes transaction execution fail, so by
+/// default the payer syncs before every payment.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncPolicy {
+    /// Sync before every payment.
+    #[default]
+    Always,
+    /// Sync only if the last sync is older than the given duration.
+    IfOlderThan(std::time::Duration),
+    /// Never sync before a payment; the caller keeps the client in sync.
+    Never,
+}
+
+#[cfg(feature = "client")]
+impl SyncPolicy {
+    /// Returns `true` if a sync is due, given the time since the last sync
+    /// (`None` if the payer has never synced).
+    pub fn should_sync(&self, since_last_sync: Option<std::time::Duration>) -> bool {
+        match self {
+            SyncPolicy::Always => true,
+            SyncPolicy::IfOlderThan(max_age) => {
+                since_last_sync.is_none_or(|elapsed| elapsed >= *max_age)
+            }
+            SyncPolicy::Never => false,
+        }
+    }
+}
+
+/// Remembers recently created payments so a retried request does not pay twice.
+///
+/// HTTP clients commonly retry a resource request from the top when the
+/// resource server times out. Without a cache, each retry would create and
+/// prove a second real payment for the same requirement. Entries are keyed by
+/// the requirement's recipient digest, asset and amount, expire after the
+/// configured window, and should be removed with
+/// [`mark_settled`](Self::mark_settled) once the server accepted the payment.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct PaymentCache {
+    window: std::time::Duration,
+    entries: std::sync::Arc<
+        std::sync::Mutex<
+            std::collections::HashMap<String, (std::time::Instant, LightweightPaymentHeader)>,
+        >,
+    >,
+}
+
+#[cfg(feature = "client")]
+impl PaymentCache {
+    /// Creates an empty cache whose entries are reused for `window`.
+    pub fn new(window: std::time::Duration) -> Self {
+        Self {
+            window,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns the payment header previously created for `requirement`, if it
+    /// is still within the window.
+    pub fn get(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Option<LightweightPaymentHeader> {
+        let mut entries = self.entries.lock().ok()?;
+        entries.retain(|_, (created_at, _)| created_at.elapsed() < self.window);
+        entries
+            .get(&Self::key(requirement))
+            .map(|(_, header)| header.clone())
+    }
+
+    /// Records the payment header created for `requirement`.
+    pub fn insert(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+        header: LightweightPaymentHeader,
+    ) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(Self::key(requirement), (std::time::Instant::now(), header));
+        }
+    }
+
+    /// Forgets the payment for `requirement` once the server has accepted it.
+    pub fn mark_settled(&self, requirement: &LightweightPaymentRequirement) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&Self::key(requirement));
+        }
+    }
+
+    pub(crate) fn key(requirement: &LightweightPaymentRequirement) -> String {
+        format!(
+            "{}:{}:{}",
+            requirement.recipient_digest, requirement.asset, requirement.amount
+        )
+    }
+}
+
+/// A payment whose transaction was submitted but whose note has not yet been
+/// seen in a block.
+#[cfg(any(feature = "miden-client-native", test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingPayment<T> {
+    note_id: String,
+    transaction_id: crate::chain::MidenTransactionId,
+    /// Hex-encoded metadata of the payment note.
+    note_metadata: String,
+    /// The submitted transaction, kept until it is recorded in the local
+    /// store so a retry can record it again.
+    unapplied: Option<T>,
+}
+
+/// Payments submitted but not yet included, shared between clones of a payer.
+///
+/// Once a payment transaction is submitted it must not be built again: a
+/// retry after a failed sync or a missing inclusion proof resumes from the
+/// recorded entry instead. Keyed like [`PaymentCache`], and kept whether or
+/// not the payer has a payment cache, until the note is included or the
+/// payment is [abandoned](Self::abandon).
+#[cfg(any(feature = "miden-client-native", test))]
+#[derive(Debug, Clone)]
+struct PendingPayments<T> {
+    entries: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, PendingPayment<T>>>>,
+}
+
+#[cfg(any(feature = "miden-client-native", test))]
+impl<T> Default for PendingPayments<T> {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+        }
+    }
+}
+
+#[cfg(any(feature = "miden-client-native", test))]
+impl<T: Clone> PendingPayments<T> {
+    /// Returns the payment pending for `requirement`, or runs `submit` and
+    /// records the payment it submitted.
+    async fn submit_or_resume<E>(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+        submit: impl Future<Output = Result<PendingPayment<T>, E>>,
+    ) -> Result<PendingPayment<T>, E> {
+        let key = PaymentCache::key(requirement);
+        if let Some(pending) = self.lock().get(&key).cloned() {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                note_id = %pending.note_id,
+                transaction_id = %pending.transaction_id,
+                "Resuming a submitted payment at the inclusion step"
+            );
+            return Ok(pending);
+        }
+
+        let pending = submit.await?;
+        self.lock().insert(key, pending.clone());
+        Ok(pending)
+    }
+
+    /// Records `pending`'s transaction in the local store with `apply`, unless
+    /// an earlier attempt already did. Until `apply` succeeds, the
+    /// transaction is kept for the next retry.
+    async fn apply<E, Fut>(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+        pending: &PendingPayment<T>,
+        apply: impl FnOnce(T) -> Fut,
+    ) -> Result<(), E>
+    where
+        Fut: Future<Output = Result<(), E>>,
+    {
+        let Some(unapplied) = pending.unapplied.clone() else {
+            return Ok(());
+        };
+        apply(unapplied).await?;
+        if let Some(entry) = self.lock().get_mut(&PaymentCache::key(requirement)) {
+            entry.unapplied = None;
+        }
+        Ok(())
+    }
+
+    /// Forgets the payment for `requirement` once its note is included.
+    fn complete(&self, requirement: &LightweightPaymentRequirement) {
+        self.lock().remove(&PaymentCache::key(requirement));
+    }
+
+    /// Forgets the payment pending for `requirement` without it being
+    /// included, returning its transaction ID.
+    fn abandon(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Option<crate::chain::MidenTransactionId> {
+        self.lock()
+            .remove(&PaymentCache::key(requirement))
+            .map(|pending| pending.transaction_id)
+    }
+
+    /// A poisoned lock is still used: losing an entry would pay twice.
+    fn lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, std::collections::HashMap<String, PendingPayment<T>>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// A slow stage of the payment flow, reported to a [`PaymentProgress`] observer.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStage {
+    /// Syncing the client with the network before building the payment.
+    Sync,
+    /// Executing, proving and submitting the payment transaction.
+    Proving,
+    /// Syncing until the payment note is included in a block.
+    AwaitingInclusion,
+}
+
+/// Progress of a payment, reported to a [`PaymentProgress`] observer.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentEvent {
+    /// A stage started.
+    Started(PaymentStage),
+    /// A stage finished successfully after `duration`.
+    Finished {
+        stage: PaymentStage,
+        duration: std::time::Duration,
+    },
+    /// A stage failed; the payment is abandoned.
+    Failed { stage: PaymentStage, error: String },
+}
+
+/// Observer of payment progress.
+///
+/// Proving takes several seconds; an observer lets a UI show which stage a
+/// payment is in, and tells a hung sync apart from a long prove.
+#[cfg(feature = "client")]
+pub trait PaymentProgress: Send + Sync {
+    /// Called for each progress event, in order.
+    fn on_event(&self, event: PaymentEvent);
+}
+
+/// Aggregate payment counters, collected as a [`PaymentProgress`] observer.
+///
+/// Install with `LightweightMidenPayer::with_progress` and read
+/// [`snapshot`](Self::snapshot) periodically, e.g. to export to Prometheus:
+///
+/// ```ignore
+/// let metrics = Arc::new(PaymentMetrics::new());
+/// let payer = LightweightMidenPayer::new(account_id, client).with_progress(metrics.clone());
+///
+/// // In the metrics exporter:
+/// let snapshot = metrics.snapshot();
+/// proving_seconds_total.set(snapshot.proving_ms_total as f64 / 1000.0);
+/// payment_failures.with_label_values(&["proving"]).set(snapshot.proving_failures as i64);
+/// ```
+///
+/// Without an observer installed, payments do not touch any counters.
+#[cfg(feature = "client")]
+#[derive(Debug, Default)]
+pub struct PaymentMetrics {
+    proofs_started: std::sync::atomic::AtomicU64,
+    proofs_completed: std::sync::atomic::AtomicU64,
+    payments_completed: std::sync::atomic::AtomicU64,
+    proving_ms_total: std::sync::atomic::AtomicU64,
+    proving_ms_max: std::sync::atomic::AtomicU64,
+    sync_failures: std::sync::atomic::AtomicU64,
+    proving_failures: std::sync::atomic::AtomicU64,
+    inclusion_failures: std::sync::atomic::AtomicU64,
+}
+
+/// Point-in-time copy of [`PaymentMetrics`].
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PaymentMetricsSnapshot {
+    /// Payment transactions that started executing and proving.
+    pub proofs_started: u64,
+    /// Payment transactions executed, proved and submitted.
+    pub proofs_completed: u64,
+    /// Payments whose note was included in a block.
+    pub payments_completed: u64,
+    /// Total time spent executing, proving and submitting, in milliseconds.
+    pub proving_ms_total: u64,
+    /// Longest single execute, prove and submit, in milliseconds.
+    pub proving_ms_max: u64,
+    /// Failed pre-payment syncs.
+    pub sync_failures: u64,
+    /// Failed or timed out executions, proofs and submissions.
+    pub proving_failures: u64,
+    /// Failures while waiting for the note to be included.
+    pub inclusion_failures: u64,
+}
+
+#[cfg(feature = "client")]
+impl PaymentMetricsSnapshot {
+    /// Returns the mean time to execute, prove and submit, in milliseconds.
+    pub fn average_proving_ms(&self) -> Option<u64> {
+        self.proving_ms_total.checked_div(self.proofs_completed)
+    }
+}
+
+#[cfg(feature = "client")]
+impl PaymentMetrics {
+    /// Creates metrics with all counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current counter values.
+    pub fn snapshot(&self) -> PaymentMetricsSnapshot {
+        use std::sync::atomic::Ordering::Relaxed;
+        PaymentMetricsSnapshot {
+            proofs_started: self.proofs_started.load(Relaxed),
+            proofs_completed: self.proofs_completed.load(Relaxed),
+            payments_completed: self.payments_completed.load(Relaxed),
+            proving_ms_total: self.proving_ms_total.load(Relaxed),
+            proving_ms_max: self.proving_ms_max.load(Relaxed),
+            sync_failures: self.sync_failures.load(Relaxed),
+            proving_failures: self.proving_failures.load(Relaxed),
+            inclusion_failures: self.inclusion_failures.load(Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+impl PaymentProgress for PaymentMetrics {
+    fn on_event(&self, event: PaymentEvent) {
+        use std::sync::atomic::Ordering::Relaxed;
+        match event {
+            PaymentEvent::Started(PaymentStage::Proving) => {
+                self.proofs_started.fetch_add(1, Relaxed);
+            }
+            PaymentEvent::Started(_) => {}
+            PaymentEvent::Finished {
+                stage: PaymentStage::Proving,
+                duration,
+            } => {
+                let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                self.proofs_completed.fetch_add(1, Relaxed);
+                self.proving_ms_total.fetch_add(ms, Relaxed);
+                self.proving_ms_max.fetch_max(ms, Relaxed);
+            }
+            PaymentEvent::Finished {
+                stage: PaymentStage::AwaitingInclusion,
+                ..
+            } => {
+                self.payments_completed.fetch_add(1, Relaxed);
+            }
+            PaymentEvent::Finished { .. } => {}
+            PaymentEvent::Failed { stage, .. } => {
+                let counter = match stage {
+                    PaymentStage::Sync => &self.sync_failures,
+                    PaymentStage::Proving => &self.proving_failures,
+                    PaymentStage::AwaitingInclusion => &self.inclusion_failures,
+                };
+                counter.fetch_add(1, Relaxed);
+            }
+        }
+    }
+}
+
+/// Runs one payment stage, reporting its start and outcome to `progress`.
+#[cfg(any(feature = "miden-client-native", test))]
+async fn observe_stage<T, E: std::fmt::Display>(
+    progress: Option<&dyn PaymentProgress>,
+    stage: PaymentStage,
+    fut: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let Some(progress) = progress else {
+        return fut.await;
+    };
+    progress.on_event(PaymentEvent::Started(stage));
+    let started = std::time::Instant::now();
+    let result = fut.await;
+    progress.on_event(match &result {
+        Ok(_) => PaymentEvent::Finished {
+            stage,
+            duration: started.elapsed(),
+        },
+        Err(e) => PaymentEvent::Failed {
+            stage,
+            error: e.to_string(),
+        },
+    });
+    result
 }
 
 // ============================================================================
 // LightweightMidenPayer — real implementation using miden-client
 // ============================================================================
 
+/// Default limit on executing and proving a payment transaction.
+///
+/// Generous enough for proving on slow machines while still failing a
+/// runaway prover (e.g. a host that is swapping) instead of hanging forever.
+#[cfg(feature = "miden-client-native")]
+pub const DEFAULT_PROVING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Awaits `fut`, failing with [`PayerError::Timeout`] if it takes longer than
+/// `limit`. The future is dropped on expiry, but only ever interrupted at an
+/// `.await`: CPU-bound work goes through [`run_blocking_until`] instead.
+#[cfg(feature = "miden-client-native")]
+async fn within_proving_timeout<T>(
+    limit: std::time::Duration,
+    fut: impl std::future::Future<Output = T>,
+) -> Result<T, PayerError> {
+    tokio::time::timeout(limit, fut)
+        .await
+        .map_err(|_| PayerError::Timeout(limit))
+}
+
+/// Runs the CPU-bound `work` on tokio's blocking thread pool, failing with
+/// `PayerError::Timeout(limit)` if it has not finished by `deadline`.
+///
+/// Blocking work cannot be interrupted: on expiry it is abandoned and runs to
+/// completion on its own thread, but the payment stops waiting for it.
+#[cfg(any(feature = "miden-client-native", test))]
+async fn run_blocking_until<T: Send + 'static>(
+    deadline: tokio::time::Instant,
+    limit: std::time::Duration,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, PayerError> {
+    match tokio::time::timeout_at(deadline, tokio::task::spawn_blocking(work)).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(join_error)) => Err(PayerError::ExecutionFailed {
+            source: join_error.into(),
+        }),
+        Err(_) => Err(PayerError::Timeout(limit)),
+    }
+}
+
 /// A lightweight payer backed by a `miden_client::Client`.
 ///
 /// This struct implements the full agent-side lightweight payment flow:
 /// create P2ID note, prove, submit to network, sync, and return the
 /// compact inclusion proof.
 ///
+/// The payer is generic over the client's authenticator (keystore), so
+/// clients using an in-memory, HSM-backed or custom keystore work too.
+/// It defaults to miden-client's `FilesystemKeyStore`.
+///
+/// Payment transactions are proved locally with miden-tx's
+/// `LocalTransactionProver`, not with a prover configured on the client, so
+/// that the [proving timeout](Self::with_proving_timeout) can bound them.
+///
 /// # Example
 ///
 /// ```ignore
@@ -86,15 +652,26 @@ pub trait LightweightPayerLike: Send + Sync {
 /// // header.note_id, header.block_num, header.inclusion_proof
 /// ```
 #[cfg(feature = "miden-client-native")]
-pub struct LightweightMidenPayer {
+pub struct LightweightMidenPayer<AUTH = miden_client::keystore::FilesystemKeyStore> {
     account_id_hex: String,
-    client: std::sync::Arc<
-        tokio::sync::Mutex<miden_client::Client<miden_client::keystore::FilesystemKeyStore>>,
-    >,
+    client: std::sync::Arc<tokio::sync::Mutex<miden_client::Client<AUTH>>>,
+    sync_policy: SyncPolicy,
+    proving_timeout: std::time::Duration,
+    payment_cache: Option<PaymentCache>,
+    pending: PendingPayments<(
+        miden_client::transaction::TransactionResult,
+        miden_protocol::block::BlockNumber,
+    )>,
+    allowed_assets: super::spending::AssetAllowlist,
+    spending_limits: super::spending::SpendingLimits,
+    spending_budget: Option<std::sync::Arc<dyn super::spending::SpendingBudget>>,
+    progress: Option<std::sync::Arc<dyn PaymentProgress>>,
+    /// When this payer last synced the client, shared between clones.
+    last_sync: std::sync::Arc<std::sync::Mutex<Option<std::time::Instant>>>,
 }
 
 #[cfg(feature = "miden-client-native")]
-impl LightweightMidenPayer {
+impl<AUTH> LightweightMidenPayer<AUTH> {
     /// Creates a new lightweight payer.
     ///
     /// # Parameters
@@ -105,39 +682,169 @@ impl LightweightMidenPayer {
     ///   ensures exclusive access during transaction execution and sync.
     pub fn new(
         account_id_hex: impl Into<String>,
-        client: std::sync::Arc<
-            tokio::sync::Mutex<miden_client::Client<miden_client::keystore::FilesystemKeyStore>>,
-        >,
+        client: std::sync::Arc<tokio::sync::Mutex<miden_client::Client<AUTH>>>,
     ) -> Self {
         Self {
             account_id_hex: account_id_hex.into(),
             client,
+            sync_policy: SyncPolicy::default(),
+            proving_timeout: DEFAULT_PROVING_TIMEOUT,
+            payment_cache: None,
+            pending: Default::default(),
+            allowed_assets: Default::default(),
+            spending_limits: Default::default(),
+            spending_budget: None,
+            progress: None,
+            last_sync: Default::default(),
+        }
+    }
+
+    /// Sets when the payer syncs chain state before building a payment.
+    ///
+    /// Defaults to [`SyncPolicy::Always`].
+    pub fn with_sync_policy(mut self, sync_policy: SyncPolicy) -> Self {
+        self.sync_policy = sync_policy;
+        self
+    }
+
+    /// Sets the limit on executing and proving a payment transaction.
+    ///
+    /// The payer proves locally, on tokio's blocking thread pool, so the
+    /// limit holds even while the CPU-bound prover runs; a prover that runs
+    /// out of time is abandoned and finishes on its thread. Submission is not
+    /// limited: once the node may have accepted the transaction, abandoning
+    /// it would leave the local store stale.
+    ///
+    /// Defaults to [`DEFAULT_PROVING_TIMEOUT`]. The time actually taken is
+    /// logged with each payment so the limit can be tuned.
+    pub fn with_proving_timeout(mut self, proving_timeout: std::time::Duration) -> Self {
+        self.proving_timeout = proving_timeout;
+        self
+    }
+
+    /// Reuses the payment created for a requirement when the same requirement
+    /// is paid again within `window`, instead of paying twice.
+    ///
+    /// Call [`mark_settled`](Self::mark_settled) once the resource server has
+    /// accepted the payment.
+    pub fn with_payment_cache(mut self, window: std::time::Duration) -> Self {
+        self.payment_cache = Some(PaymentCache::new(window));
+        self
+    }
+
+    /// Refuses payments above the given per-payment caps.
+    ///
+    /// Checked before any transaction is executed or proved.
+    pub fn with_spending_limits(mut self, limits: super::spending::SpendingLimits) -> Self {
+        self.spending_limits = limits;
+        self
+    }
+
+    /// Only pays in faucets on `allowlist`; other requirements are refused
+    /// before any work is done.
+    pub fn with_allowed_assets(mut self, allowlist: super::spending::AssetAllowlist) -> Self {
+        self.allowed_assets = allowlist;
+        self
+    }
+
+    /// Tracks cumulative spending and refuses payments that would exceed the
+    /// budget.
+    ///
+    /// The amount is reserved before the payment transaction is executed or
+    /// proved, committed once it is submitted and released otherwise, so
+    /// payers sharing one budget (e.g. in a [`super::PayerPool`]) cannot
+    /// overspend it together.
+    pub fn with_spending_budget(
+        mut self,
+        budget: std::sync::Arc<dyn super::spending::SpendingBudget>,
+    ) -> Self {
+        self.spending_budget = Some(budget);
+        self
+    }
+
+    /// Reports the progress of each payment to `progress`.
+    pub fn with_progress(mut self, progress: std::sync::Arc<dyn PaymentProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Forgets the cached payment for `requirement` after the resource
+    /// server accepted it. No-op without a payment cache.
+    pub fn mark_settled(&self, requirement: &LightweightPaymentRequirement) {
+        if let Some(cache) = &self.payment_cache {
+            cache.mark_settled(requirement);
+        }
+    }
+
+    /// Gives up on a payment for `requirement` that was submitted but never
+    /// included, returning its transaction ID, so the next attempt pays anew.
+    ///
+    /// Only call this once the transaction can no longer be committed (e.g.
+    /// it expired); otherwise the requirement may be paid twice.
+    pub fn abandon_pending_payment(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Option<crate::chain::MidenTransactionId> {
+        self.pending.abandon(requirement)
+    }
+
+    /// Returns the time since this payer last synced the client.
+    fn time_since_last_sync(&self) -> Option<std::time::Duration> {
+        self.last_sync
+            .lock()
+            .ok()
+            .and_then(|last| last.map(|at| at.elapsed()))
+    }
+
+    /// Records that the client was just synced.
+    fn mark_synced(&self) {
+        if let Ok(mut last) = self.last_sync.lock() {
+            *last = Some(std::time::Instant::now());
         }
     }
 }
 
 #[cfg(feature = "miden-client-native")]
-impl std::fmt::Debug for LightweightMidenPayer {
+impl<AUTH> std::fmt::Debug for LightweightMidenPayer<AUTH> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LightweightMidenPayer")
             .field("account_id_hex", &self.account_id_hex)
+            .field("sync_policy", &self.sync_policy)
+            .field("proving_timeout", &self.proving_timeout)
+            .field("payment_cache", &self.payment_cache.is_some())
+            .field("allowed_assets", &self.allowed_assets)
+            .field("spending_limits", &self.spending_limits)
+            .field("spending_budget", &self.spending_budget)
+            .field("progress", &self.progress.is_some())
             .finish_non_exhaustive()
     }
 }
 
 #[cfg(feature = "miden-client-native")]
-impl Clone for LightweightMidenPayer {
+impl<AUTH> Clone for LightweightMidenPayer<AUTH> {
     fn clone(&self) -> Self {
         Self {
             account_id_hex: self.account_id_hex.clone(),
             client: self.client.clone(),
+            sync_policy: self.sync_policy,
+            proving_timeout: self.proving_timeout,
+            payment_cache: self.payment_cache.clone(),
+            pending: self.pending.clone(),
+            allowed_assets: self.allowed_assets.clone(),
+            spending_limits: self.spending_limits.clone(),
+            spending_budget: self.spending_budget.clone(),
+            progress: self.progress.clone(),
+            last_sync: self.last_sync.clone(),
         }
     }
 }
 
 #[cfg(feature = "miden-client-native")]
 #[async_trait::async_trait]
-impl LightweightPayerLike for LightweightMidenPayer {
+impl<AUTH> LightweightPayerLike for LightweightMidenPayer<AUTH>
+where
+    AUTH: miden_tx::auth::TransactionAuthenticator + Send + Sync + 'static,
+{
     fn account_id(&self) -> String {
         self.account_id_hex.clone()
     }
@@ -146,6 +853,39 @@ impl LightweightPayerLike for LightweightMidenPayer {
         &self,
         requirement: &LightweightPaymentRequirement,
     ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error> {
+        self.submit_payment(requirement)
+            .await
+            .map(|(header, _)| header)
+    }
+
+    async fn create_and_submit_payment_with_receipt(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<(LightweightPaymentHeader, PaymentReceipt), x402_types::scheme::client::X402Error>
+    {
+        let (header, transaction_id) = self.submit_payment(requirement).await?;
+        let receipt = PaymentReceipt::new(self.account_id(), requirement, &header, transaction_id);
+        Ok((header, receipt))
+    }
+}
+
+#[cfg(feature = "miden-client-native")]
+impl<AUTH> LightweightMidenPayer<AUTH>
+where
+    AUTH: miden_tx::auth::TransactionAuthenticator + Send + Sync + 'static,
+{
+    /// Runs the payment flow, returning the header and, unless the payment
+    /// was reused from the cache, the ID of the payment transaction.
+    async fn submit_payment(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<
+        (
+            LightweightPaymentHeader,
+            Option<crate::chain::MidenTransactionId>,
+        ),
+        x402_types::scheme::client::X402Error,
+    > {
         use miden_client::note::build_p2id_recipient;
         use miden_protocol::Word;
         use miden_protocol::account::AccountId;
@@ -153,116 +893,274 @@ impl LightweightPayerLike for LightweightMidenPayer {
         use miden_protocol::note::{Note, NoteAssets, NoteMetadata, NoteTag, NoteType};
         use miden_protocol::transaction::OutputNote;
         use miden_protocol::utils::serde::Serializable;
-        use x402_types::scheme::client::X402Error;
 
-        // 1. Parse account IDs
-        let sender = AccountId::from_hex(&self.account_id_hex)
-            .map_err(|e| X402Error::SigningError(format!("Invalid sender account ID: {e}")))?;
-        let target = AccountId::from_hex(&requirement.pay_to).map_err(|e| {
-            X402Error::SigningError(format!("Invalid target account ID (pay_to): {e}"))
-        })?;
-        let faucet = AccountId::from_hex(&requirement.asset)
-            .map_err(|e| X402Error::SigningError(format!("Invalid faucet account ID: {e}")))?;
-
-        // 2. Parse server's serial_num from hex into Word ([Felt; 4])
-        let serial_num_hex = requirement.serial_num.as_deref().ok_or_else(|| {
-            X402Error::SigningError(
-                "serial_num is required in LightweightPaymentRequirement for note construction"
-                    .into(),
-            )
-        })?;
-        let serial_num: Word =
-            super::types::parse_serial_num_hex(serial_num_hex).map_err(X402Error::SigningError)?;
-
-        // 3. Build P2ID NoteRecipient with the server's serial_num
-        //    This ensures the note's recipient_digest matches what the server expects.
-        let recipient = build_p2id_recipient(target, serial_num)
-            .map_err(|e| X402Error::SigningError(format!("Failed to build P2ID recipient: {e}")))?;
-
-        // 4. Build the Note manually with the custom recipient
-        let asset = FungibleAsset::new(faucet, requirement.amount)
-            .map_err(|e| X402Error::SigningError(format!("Failed to create FungibleAsset: {e}")))?;
-        let vault = NoteAssets::new(vec![Asset::Fungible(asset)])
-            .map_err(|e| X402Error::SigningError(format!("Invalid note assets: {e}")))?;
-
-        let tag = NoteTag::new(requirement.note_tag);
-        let metadata = NoteMetadata::new(sender, NoteType::Private, tag);
-
-        let note = Note::new(vault, metadata, recipient);
-        let note_id_str = format!("{}", note.id());
-
-        // 5. Build transaction request with our custom note (bypassing build_pay_to_id
-        //    which would generate its own serial_num)
-        let tx_request = miden_client::transaction::TransactionRequestBuilder::new()
-            .own_output_notes(vec![OutputNote::Full(note)])
-            .build()
-            .map_err(|e| {
-                X402Error::SigningError(format!("Failed to build TransactionRequest: {e}"))
-            })?;
+        // 0. A retry of a requirement we already paid reuses that payment.
+        if let Some(header) = self.payment_cache.as_ref().and_then(|c| c.get(requirement)) {
+            return Ok((header, None));
+        }
+
+        // 0b. Refuse assets not on the allowlist and amounts above the
+        //     configured caps before doing any work.
+        if let Err(e) = self.allowed_assets.check(requirement) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                asset = %requirement.asset,
+                network = %requirement.network,
+                "Refusing payment in an asset that is not allowlisted"
+            );
+            return Err(e.into());
+        }
+        self.spending_limits.check(requirement)?;
 
-        // 6. Execute, prove, submit, and apply the transaction in one call.
-        //    submit_new_transaction handles the full lifecycle:
-        //      execute_transaction -> prove_transaction -> submit_proven_transaction -> apply_transaction
         let mut client_guard = self.client.lock().await;
-        client_guard
-            .submit_new_transaction(sender, tx_request)
+
+        //    A concurrent call for the same requirement may have paid it
+        //    while we were waiting for the client.
+        if let Some(header) = self.payment_cache.as_ref().and_then(|c| c.get(requirement)) {
+            return Ok((header, None));
+        }
+
+        // 1. Build, execute, prove and submit the payment transaction, unless
+        //    an earlier attempt already submitted it; that attempt is resumed
+        //    at the inclusion step below instead of paying twice.
+        let pending = self
+            .pending
+            .submit_or_resume(requirement, async {
+                //    Reserve the amount up front, so payers sharing the budget
+                //    cannot both spend its last allowance. The reservation is
+                //    released if the payment is not submitted.
+                let reservation = self
+                    .spending_budget
+                    .clone()
+                    .map(|budget| {
+                        super::spending::SpendingReservation::new(
+                            budget,
+                            &requirement.asset,
+                            requirement.amount,
+                        )
+                    })
+                    .transpose()?;
+
+                //    Bring the local account state up to date first if the sync
+                //    policy says so; executing against stale state fails.
+                if self.sync_policy.should_sync(self.time_since_last_sync()) {
+                    let summary =
+                        observe_stage(self.progress.as_deref(), PaymentStage::Sync, async {
+                            client_guard
+                                .sync_state()
+                                .await
+                                .map_err(|e| PayerError::SyncFailed { source: e.into() })
+                        })
+                        .await?;
+                    self.mark_synced();
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        block_num = summary.block_num.as_u32(),
+                        "Synced client state before payment"
+                    );
+                }
+
+                // 2. Parse account IDs
+                let sender = AccountId::from_hex(&self.account_id_hex)
+                    .map_err(|e| PayerError::InvalidSender(e.to_string()))?;
+                let target = AccountId::from_hex(&requirement.pay_to)
+                    .map_err(|e| PayerError::InvalidRecipient(e.to_string()))?;
+                let faucet = AccountId::from_hex(&requirement.asset)
+                    .map_err(|e| PayerError::InvalidFaucet(e.to_string()))?;
+
+                // 3. Parse server's serial_num from hex into Word ([Felt; 4])
+                let serial_num_hex = requirement.serial_num.as_deref().ok_or_else(|| {
+                    PayerError::InvalidRequirement(
+                        "serial_num is required in LightweightPaymentRequirement for note construction"
+                            .into(),
+                    )
+                })?;
+                let serial_num: Word = super::types::parse_serial_num_hex(serial_num_hex)
+                    .map_err(PayerError::InvalidRequirement)?;
+
+                // 4. Build P2ID NoteRecipient with the server's serial_num
+                //    This ensures the note's recipient_digest matches what the server expects.
+                let recipient = build_p2id_recipient(target, serial_num).map_err(|e| {
+                    PayerError::InvalidRequirement(format!("Failed to build P2ID recipient: {e}"))
+                })?;
+
+                // 5. Build the Note manually with the custom recipient
+                let asset = FungibleAsset::new(faucet, requirement.amount).map_err(|e| {
+                    PayerError::InvalidRequirement(format!("Failed to create FungibleAsset: {e}"))
+                })?;
+                let vault = NoteAssets::new(vec![Asset::Fungible(asset)]).map_err(|e| {
+                    PayerError::InvalidRequirement(format!("Invalid note assets: {e}"))
+                })?;
+
+                let tag = NoteTag::new(requirement.note_tag);
+                let metadata = NoteMetadata::new(sender, NoteType::Private, tag);
+
+                let note = Note::new(vault, metadata.clone(), recipient);
+                let note_id = format!("{}", note.id());
+
+                // 6. Build transaction request with our custom note (bypassing
+                //    build_pay_to_id which would generate its own serial_num)
+                let tx_request = miden_client::transaction::TransactionRequestBuilder::new()
+                    .own_output_notes(vec![OutputNote::Full(note)])
+                    .build()
+                    .map_err(|e| {
+                        PayerError::InvalidRequirement(format!(
+                            "Failed to build TransactionRequest: {e}"
+                        ))
+                    })?;
+
+                // 7. Execute, prove and submit the transaction. These are the
+                //    steps of submit_new_transaction, run one by one so that
+                //    only execution and proving are time-limited.
+                let proving_started = std::time::Instant::now();
+                let deadline = tokio::time::Instant::now() + self.proving_timeout;
+                let (tx_result, submission_height) =
+                    observe_stage(self.progress.as_deref(), PaymentStage::Proving, async {
+                        let tx_result = within_proving_timeout(
+                            self.proving_timeout,
+                            client_guard.execute_transaction(sender, tx_request),
+                        )
+                        .await?
+                        .map_err(|e| PayerError::ExecutionFailed { source: e.into() })?;
+
+                        //    Proving is CPU-bound and never yields, so it runs
+                        //    on its own thread where the deadline still holds.
+                        let tx_inputs: miden_protocol::transaction::TransactionInputs =
+                            tx_result.executed_transaction().clone().into();
+                        let proven_transaction =
+                            run_blocking_until(deadline, self.proving_timeout, move || {
+                                miden_tx::LocalTransactionProver::default().prove(tx_inputs)
+                            })
+                            .await?
+                            .map_err(|e| PayerError::ExecutionFailed { source: e.into() })?;
+
+                        // Not time-limited: dropping a submission the node may
+                        // already have accepted would leave the store stale.
+                        let submission_height = client_guard
+                            .submit_proven_transaction(proven_transaction, &tx_result)
+                            .await
+                            .map_err(|e| PayerError::ExecutionFailed { source: e.into() })?;
+                        Ok::<_, PayerError>((tx_result, submission_height))
+                    })
+                    .await?;
+
+                if let Some(reservation) = reservation {
+                    reservation.commit();
+                }
+
+                #[cfg(feature = "tracing")]
+                tracing::info!(
+                    proving_ms = proving_started.elapsed().as_millis() as u64,
+                    timeout_secs = self.proving_timeout.as_secs(),
+                    "Payment transaction executed, proved and submitted"
+                );
+
+                Ok::<_, x402_types::scheme::client::X402Error>(PendingPayment {
+                    note_id,
+                    transaction_id: tx_result.executed_transaction().id().into(),
+                    note_metadata: format!("0x{}", hex::encode(metadata.to_bytes())),
+                    unapplied: Some((tx_result, submission_height)),
+                })
+            })
+            .await?;
+
+        //    From here on the payment is submitted: failures are reported
+        //    with its transaction ID and resumed on retry.
+        let transaction_id = pending.transaction_id;
+        let awaiting_inclusion = |source: PayerError| PayerError::AwaitingInclusion {
+            transaction_id,
+            source: Box::new(source),
+        };
+
+        //    Record the submitted transaction and its output note locally,
+        //    so the sync below picks up the note's inclusion proof. A retry
+        //    records it again if an earlier attempt failed to.
+        let client = &*client_guard;
+        self.pending
+            .apply(
+                requirement,
+                &pending,
+                |(tx_result, submission_height)| async move {
+                    client
+                        .apply_transaction(&tx_result, submission_height)
+                        .await
+                },
+            )
             .await
-            .map_err(|e| X402Error::SigningError(format!("Transaction submission failed: {e}")))?;
+            .map_err(|e| awaiting_inclusion(PayerError::Store { source: e.into() }))?;
 
-        // 7. Sync state to get the note inclusion proof from the network.
+        // 8. Sync state to get the note inclusion proof from the network.
         //    After the transaction is committed to a block, sync_state will
         //    update the local store with inclusion proofs for output notes.
-        client_guard
-            .sync_state()
-            .await
-            .map_err(|e| X402Error::SigningError(format!("State sync failed: {e}")))?;
+        observe_stage(
+            self.progress.as_deref(),
+            PaymentStage::AwaitingInclusion,
+            async {
+                client_guard
+                    .sync_state()
+                    .await
+                    .map_err(|e| PayerError::SyncFailed { source: e.into() })
+            },
+        )
+        .await
+        .map_err(awaiting_inclusion)?;
+        self.mark_synced();
 
-        // 8. Extract the inclusion proof from the client's output note store.
+        // 9. Extract the inclusion proof from the client's output note store.
         //    After sync, committed notes have inclusion proofs attached.
         let output_notes = client_guard
             .get_output_notes(miden_client::store::NoteFilter::Committed)
             .await
-            .map_err(|e| X402Error::SigningError(format!("Failed to query output notes: {e}")))?;
+            .map_err(|e| awaiting_inclusion(PayerError::Store { source: e.into() }))?;
 
         let our_note = output_notes
             .iter()
-            .find(|n| format!("{}", n.id()) == note_id_str)
+            .find(|n| format!("{}", n.id()) == pending.note_id)
             .ok_or_else(|| {
-                X402Error::SigningError(
+                awaiting_inclusion(PayerError::NoteNotCommitted(
                     "Note not found in client store after sync — \
                      the transaction may not yet be committed to a block"
                         .into(),
-                )
+                ))
             })?;
 
         let inclusion_proof = our_note.inclusion_proof().ok_or_else(|| {
-            X402Error::SigningError(
+            awaiting_inclusion(PayerError::NoteNotCommitted(
                 "Note has no inclusion proof yet — may need additional sync cycles".into(),
-            )
+            ))
         })?;
 
         let block_num = inclusion_proof.location().block_num().as_u32();
         let note_index = inclusion_proof.location().node_index_in_block();
         let path_bytes = inclusion_proof.note_path().to_bytes();
         let path_hex = format!("0x{}", hex::encode(&path_bytes));
-        let metadata_bytes = metadata.to_bytes();
-        let metadata_hex = format!("0x{}", hex::encode(&metadata_bytes));
-
-        drop(client_guard);
 
-        Ok(LightweightPaymentHeader {
-            note_id: note_id_str,
+        let header = LightweightPaymentHeader {
+            payload_version: super::types::LIGHTWEIGHT_PAYLOAD_VERSION,
+            note_id: pending.note_id,
             block_num,
             note_index,
-            note_metadata: metadata_hex,
+            note_metadata: pending.note_metadata,
             inclusion_proof: path_hex,
-        })
+        };
+
+        // Cache before releasing the client so concurrent callers for the
+        // same requirement find this payment.
+        if let Some(cache) = &self.payment_cache {
+            cache.insert(requirement, header.clone());
+        }
+        self.pending.complete(requirement);
+        drop(client_guard);
+
+        Ok((header, Some(transaction_id)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lightweight::test_fixtures::{MockPayer, header, requirement};
 
     #[test]
     fn test_requirement_with_pay_to_and_serial_num() {
@@ -299,4 +1197,602 @@ mod tests {
         };
         assert!(req.serial_num.is_none());
     }
+
+    /// The payer works with any authenticator, not just the filesystem keystore.
+    #[cfg(feature = "miden-client-native")]
+    #[test]
+    fn test_payer_is_generic_over_keystore() {
+        fn assert_payer<T: LightweightPayerLike + Clone + std::fmt::Debug>() {}
+
+        assert_payer::<LightweightMidenPayer>();
+        assert_payer::<LightweightMidenPayer<miden_client::keystore::FilesystemKeyStore>>();
+        assert_payer::<LightweightMidenPayer<()>>();
+    }
+
+    #[cfg(feature = "miden-client-native")]
+    #[tokio::test]
+    async fn test_within_proving_timeout_expires() {
+        use std::time::Duration;
+
+        let result = within_proving_timeout(
+            Duration::from_millis(10),
+            tokio::time::sleep(Duration::from_secs(5)),
+        )
+        .await;
+        let err = result.unwrap_err();
+        assert!(err.is_retryable());
+        let err = err.to_string();
+        assert!(err.contains("Proving timed out after 0s"), "{err}");
+    }
+
+    #[cfg(feature = "miden-client-native")]
+    #[tokio::test]
+    async fn test_within_proving_timeout_passes_through() {
+        use std::time::Duration;
+
+        let result = within_proving_timeout(Duration::from_secs(5), async { 42 }).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_prover_is_bounded_by_deadline() {
+        use std::time::{Duration, Instant};
+
+        // A prover that never yields, like local STARK proving on a host
+        // that is swapping.
+        let limit = Duration::from_millis(50);
+        let started = Instant::now();
+        let result = run_blocking_until(tokio::time::Instant::now() + limit, limit, || {
+            std::thread::sleep(Duration::from_secs(1));
+            42
+        })
+        .await;
+
+        assert!(matches!(result, Err(PayerError::Timeout(l)) if l == limit));
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_blocking_prover_result_and_panic() {
+        use std::time::Duration;
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        let result = run_blocking_until(deadline, Duration::from_secs(5), || 42).await;
+        assert_eq!(result.unwrap(), 42);
+
+        let result = run_blocking_until(deadline, Duration::from_secs(5), || -> u8 {
+            panic!("prover crashed")
+        })
+        .await;
+        assert!(matches!(result, Err(PayerError::ExecutionFailed { .. })));
+    }
+
+    #[test]
+    fn test_payment_cache_reuses_payment_for_same_requirement() {
+        let cache = PaymentCache::new(std::time::Duration::from_secs(60));
+        let req = requirement();
+        assert!(cache.get(&req).is_none());
+
+        cache.insert(&req, header("0xnote1"));
+        assert_eq!(cache.get(&req).unwrap().note_id, "0xnote1");
+
+        // A different requirement (new serial number -> new digest) is not reused.
+        let other_digest = LightweightPaymentRequirement {
+            recipient_digest: "0xdigest2".to_string(),
+            ..req.clone()
+        };
+        assert!(cache.get(&other_digest).is_none());
+
+        // Neither is the same digest at a different amount.
+        let mut other_amount = req.clone();
+        other_amount.amount += 1;
+        assert!(cache.get(&other_amount).is_none());
+    }
+
+    #[test]
+    fn test_payment_cache_mark_settled_and_expiry() {
+        let cache = PaymentCache::new(std::time::Duration::from_secs(60));
+        let req = requirement();
+        cache.insert(&req, header("0xnote1"));
+        cache.mark_settled(&req);
+        assert!(cache.get(&req).is_none());
+
+        let expired = PaymentCache::new(std::time::Duration::ZERO);
+        expired.insert(&req, header("0xnote1"));
+        assert!(expired.get(&req).is_none());
+    }
+
+    #[test]
+    fn test_payment_cache_shared_across_clones_and_threads() {
+        let cache = PaymentCache::new(std::time::Duration::from_secs(60));
+        let req = requirement();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let cache = cache.clone();
+                let req = req.clone();
+                std::thread::spawn(move || {
+                    if cache.get(&req).is_none() {
+                        cache.insert(&req, header(&format!("0xnote{i}")));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Every clone sees one payment for the requirement.
+        let note_id = cache.get(&req).unwrap().note_id;
+        assert_eq!(cache.clone().get(&req).unwrap().note_id, note_id);
+    }
+
+    fn pending_payment(note_id: &str) -> PendingPayment<&'static str> {
+        PendingPayment {
+            note_id: note_id.to_string(),
+            transaction_id: crate::chain::MidenTransactionId::from_bytes([7; 32]),
+            note_metadata: "0xaabb".to_string(),
+            unapplied: Some("submitted transaction"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_payment_resumed_instead_of_resubmitted() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let pending = PendingPayments::default();
+        let req = requirement();
+        let submissions = AtomicUsize::new(0);
+        let submit = |note_id: &'static str| async {
+            submissions.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, PayerError>(pending_payment(note_id))
+        };
+
+        // The first attempt submits and records the payment, then fails to
+        // find the note included.
+        let first = pending
+            .submit_or_resume(&req, submit("0xnote1"))
+            .await
+            .unwrap();
+        assert_eq!(first, pending_payment("0xnote1"));
+
+        // A retry, also from a clone of the payer, resumes that payment
+        // without building or submitting a new transaction.
+        let retry = pending
+            .clone()
+            .submit_or_resume(&req, submit("0xnote2"))
+            .await
+            .unwrap();
+        assert_eq!(retry, first);
+        assert_eq!(submissions.load(Ordering::SeqCst), 1);
+
+        // Once the note is included, the next payment is new.
+        pending.complete(&req);
+        let next = pending
+            .submit_or_resume(&req, submit("0xnote3"))
+            .await
+            .unwrap();
+        assert_eq!(next.note_id, "0xnote3");
+        assert_eq!(submissions.load(Ordering::SeqCst), 2);
+    }
+
+    async fn must_not_resubmit() -> Result<PendingPayment<&'static str>, PayerError> {
+        unreachable!("a pending payment must not be submitted again")
+    }
+
+    #[tokio::test]
+    async fn test_failed_apply_is_retried_on_resume() {
+        let pending = PendingPayments::default();
+        let req = requirement();
+        let submitted = pending
+            .submit_or_resume(&req, async {
+                Ok::<_, PayerError>(pending_payment("0xnote1"))
+            })
+            .await
+            .unwrap();
+
+        // Recording the transaction in the local store fails after submission.
+        let failed = pending
+            .apply(&req, &submitted, |_| async { Err("store unavailable") })
+            .await;
+        assert_eq!(failed, Err("store unavailable"));
+
+        // The retry still has the transaction and records it.
+        let retry = pending
+            .submit_or_resume(&req, must_not_resubmit())
+            .await
+            .unwrap();
+        let mut applied = None;
+        pending
+            .apply(&req, &retry, |tx| {
+                applied = Some(tx);
+                async { Ok::<_, &str>(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(applied, Some("submitted transaction"));
+
+        // Later retries do not record it twice.
+        let again = pending
+            .submit_or_resume(&req, must_not_resubmit())
+            .await
+            .unwrap();
+        assert_eq!(again.unapplied, None);
+        pending
+            .apply::<&str, _>(&req, &again, |_| async {
+                unreachable!("a recorded transaction must not be applied again")
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_payment_is_paid_anew() {
+        let pending = PendingPayments::default();
+        let req = requirement();
+        pending
+            .submit_or_resume(&req, async {
+                Ok::<_, PayerError>(pending_payment("0xnote1"))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pending.abandon(&req),
+            Some(crate::chain::MidenTransactionId::from_bytes([7; 32]))
+        );
+        assert_eq!(pending.abandon(&req), None);
+
+        let next = pending
+            .submit_or_resume(&req, async {
+                Ok::<_, PayerError>(pending_payment("0xnote2"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(next.note_id, "0xnote2");
+    }
+
+    #[tokio::test]
+    async fn test_failed_submission_is_not_pending() {
+        let pending = PendingPayments::default();
+        let req = requirement();
+
+        let failed = pending
+            .submit_or_resume(&req, async {
+                Err::<PendingPayment<&str>, _>(PayerError::Timeout(std::time::Duration::from_secs(
+                    1,
+                )))
+            })
+            .await;
+        assert!(matches!(failed, Err(PayerError::Timeout(_))));
+
+        let retry = pending
+            .submit_or_resume(&req, async {
+                Ok::<_, PayerError>(pending_payment("0xnote1"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(retry.note_id, "0xnote1");
+    }
+
+    #[test]
+    fn test_payer_error_classification() {
+        use std::error::Error;
+
+        let io = || Box::new(std::io::Error::other("connection reset"));
+
+        let retryable = [
+            PayerError::SyncFailed { source: io() },
+            PayerError::Timeout(std::time::Duration::from_secs(120)),
+            PayerError::AwaitingInclusion {
+                transaction_id: crate::chain::MidenTransactionId::from_bytes([7; 32]),
+                source: Box::new(PayerError::NoteNotCommitted(
+                    "Note not found in client store after sync".into(),
+                )),
+            },
+        ];
+        for err in &retryable {
+            assert!(err.is_retryable(), "{err}");
+            assert!(err.to_string().contains("(retryable)"), "{err}");
+        }
+
+        let permanent = [
+            PayerError::InvalidSender("bad hex".into()),
+            PayerError::InvalidRecipient("bad hex".into()),
+            PayerError::InvalidFaucet("bad hex".into()),
+            PayerError::UnsupportedNetwork("miden:mainnet".into()),
+            PayerError::InvalidRequirement("serial_num is required".into()),
+            PayerError::ExecutionFailed { source: io() },
+            PayerError::Store { source: io() },
+            PayerError::NoteNotCommitted("Note not found in client store after sync".into()),
+        ];
+        for err in &permanent {
+            assert!(!err.is_retryable(), "{err}");
+            assert!(!err.to_string().contains("(retryable)"), "{err}");
+        }
+
+        // The underlying error stays reachable as the source.
+        let err = PayerError::ExecutionFailed { source: io() };
+        assert_eq!(err.source().unwrap().to_string(), "connection reset");
+        assert_eq!(
+            err.to_string(),
+            "Transaction submission failed: connection reset"
+        );
+
+        // A submitted payment names its transaction.
+        let err = PayerError::AwaitingInclusion {
+            transaction_id: crate::chain::MidenTransactionId::from_bytes([0xab; 32]),
+            source: Box::new(PayerError::Store { source: io() }),
+        };
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Transaction 0x{} submitted, awaiting inclusion (retryable): \
+                 Failed to query output notes: connection reset",
+                "ab".repeat(32)
+            )
+        );
+        assert!(matches!(
+            err.source().unwrap().downcast_ref::<PayerError>(),
+            Some(PayerError::Store { .. })
+        ));
+    }
+
+    #[test]
+    fn test_payer_error_into_x402_error() {
+        let err: x402_types::scheme::client::X402Error =
+            PayerError::InvalidRecipient("bad hex".into()).into();
+        assert!(matches!(
+            err,
+            x402_types::scheme::client::X402Error::SigningError(ref msg)
+                if msg == "Invalid target account ID (pay_to): bad hex"
+        ));
+    }
+
+    #[derive(Default)]
+    struct RecordingProgress(std::sync::Mutex<Vec<PaymentEvent>>);
+
+    impl PaymentProgress for RecordingProgress {
+        fn on_event(&self, event: PaymentEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    impl RecordingProgress {
+        /// Events with durations zeroed, for comparison.
+        fn events(&self) -> Vec<PaymentEvent> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .cloned()
+                .map(|event| match event {
+                    PaymentEvent::Finished { stage, .. } => PaymentEvent::Finished {
+                        stage,
+                        duration: std::time::Duration::ZERO,
+                    },
+                    other => other,
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_events_in_order() {
+        let progress = RecordingProgress::default();
+        let observer: Option<&dyn PaymentProgress> = Some(&progress);
+
+        observe_stage(observer, PaymentStage::Sync, async { Ok::<_, String>(()) })
+            .await
+            .unwrap();
+        observe_stage(observer, PaymentStage::Proving, async {
+            Ok::<_, String>(())
+        })
+        .await
+        .unwrap();
+        let err = observe_stage(observer, PaymentStage::AwaitingInclusion, async {
+            Err::<(), _>("State sync failed: node unavailable".to_string())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err, "State sync failed: node unavailable");
+
+        let finished = |stage| PaymentEvent::Finished {
+            stage,
+            duration: std::time::Duration::ZERO,
+        };
+        assert_eq!(
+            progress.events(),
+            vec![
+                PaymentEvent::Started(PaymentStage::Sync),
+                finished(PaymentStage::Sync),
+                PaymentEvent::Started(PaymentStage::Proving),
+                finished(PaymentStage::Proving),
+                PaymentEvent::Started(PaymentStage::AwaitingInclusion),
+                PaymentEvent::Failed {
+                    stage: PaymentStage::AwaitingInclusion,
+                    error: "State sync failed: node unavailable".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_reports_stage_duration() {
+        let progress = RecordingProgress::default();
+        observe_stage(Some(&progress), PaymentStage::Proving, async {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            Ok::<_, String>(())
+        })
+        .await
+        .unwrap();
+
+        let events = progress.0.lock().unwrap();
+        match &events[1] {
+            PaymentEvent::Finished { stage, duration } => {
+                assert_eq!(*stage, PaymentStage::Proving);
+                assert!(*duration >= std::time::Duration::from_millis(20));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_without_progress_stage_still_runs() {
+        let result = observe_stage(None, PaymentStage::Sync, async { Ok::<_, String>(7) }).await;
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[test]
+    fn test_payment_metrics_counts_events() {
+        use std::time::Duration;
+
+        let metrics = PaymentMetrics::new();
+        assert_eq!(metrics.snapshot(), PaymentMetricsSnapshot::default());
+        assert_eq!(metrics.snapshot().average_proving_ms(), None);
+
+        // A successful payment.
+        for event in [
+            PaymentEvent::Started(PaymentStage::Sync),
+            PaymentEvent::Finished {
+                stage: PaymentStage::Sync,
+                duration: Duration::from_millis(200),
+            },
+            PaymentEvent::Started(PaymentStage::Proving),
+            PaymentEvent::Finished {
+                stage: PaymentStage::Proving,
+                duration: Duration::from_secs(12),
+            },
+            PaymentEvent::Started(PaymentStage::AwaitingInclusion),
+            PaymentEvent::Finished {
+                stage: PaymentStage::AwaitingInclusion,
+                duration: Duration::from_secs(3),
+            },
+        ] {
+            metrics.on_event(event);
+        }
+        // A payment whose note is never seen, then one that fails to prove.
+        metrics.on_event(PaymentEvent::Started(PaymentStage::Proving));
+        metrics.on_event(PaymentEvent::Finished {
+            stage: PaymentStage::Proving,
+            duration: Duration::from_secs(8),
+        });
+        metrics.on_event(PaymentEvent::Failed {
+            stage: PaymentStage::AwaitingInclusion,
+            error: "State sync failed (retryable): node unavailable".into(),
+        });
+        metrics.on_event(PaymentEvent::Started(PaymentStage::Proving));
+        metrics.on_event(PaymentEvent::Failed {
+            stage: PaymentStage::Proving,
+            error: "Proving timed out after 120s (retryable)".into(),
+        });
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot,
+            PaymentMetricsSnapshot {
+                proofs_started: 3,
+                proofs_completed: 2,
+                payments_completed: 1,
+                proving_ms_total: 20_000,
+                proving_ms_max: 12_000,
+                sync_failures: 0,
+                proving_failures: 1,
+                inclusion_failures: 1,
+            }
+        );
+        assert_eq!(snapshot.average_proving_ms(), Some(10_000));
+    }
+
+    #[tokio::test]
+    async fn test_payment_metrics_as_stage_observer() {
+        let metrics = PaymentMetrics::new();
+        observe_stage(Some(&metrics), PaymentStage::Proving, async {
+            Ok::<_, String>(())
+        })
+        .await
+        .unwrap();
+        observe_stage(Some(&metrics), PaymentStage::Sync, async {
+            Err::<(), _>("node unavailable".to_string())
+        })
+        .await
+        .unwrap_err();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.proofs_started, 1);
+        assert_eq!(snapshot.proofs_completed, 1);
+        assert_eq!(snapshot.sync_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_default_payment_receipt() {
+        let req = requirement();
+        let (header, receipt) = MockPayer::new("0x0b50cc0489f8f1101e946691aa89ca")
+            .create_and_submit_payment_with_receipt(&req)
+            .await
+            .unwrap();
+
+        assert_eq!(header.note_id, "0xnote-0x0b50cc0489f8f1101e946691aa89ca");
+        assert_eq!(receipt.note_id, header.note_id);
+        assert_eq!(receipt.block_num, header.block_num);
+        assert_eq!(receipt.payer, "0x0b50cc0489f8f1101e946691aa89ca");
+        assert_eq!(receipt.pay_to, req.pay_to);
+        assert_eq!(receipt.faucet, req.asset);
+        assert_eq!(receipt.amount, req.amount);
+        assert_eq!(receipt.network, req.network);
+        assert!(receipt.transaction_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_default_payment_receipt_names_the_network_account() {
+        /// Pays testnet requirements from a dedicated account.
+        struct PerNetworkPayer(std::sync::Arc<MockPayer>);
+
+        #[async_trait::async_trait]
+        impl LightweightPayerLike for PerNetworkPayer {
+            fn account_id(&self) -> String {
+                self.0.account_id()
+            }
+
+            fn account_id_for(&self, network: &x402_types::chain::ChainId) -> Option<String> {
+                (network.reference == "testnet").then(|| "0xtestnet".to_string())
+            }
+
+            async fn create_and_submit_payment(
+                &self,
+                requirement: &LightweightPaymentRequirement,
+            ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error>
+            {
+                self.0.create_and_submit_payment(requirement).await
+            }
+        }
+
+        let (_, receipt) = PerNetworkPayer(MockPayer::new("0xdefault"))
+            .create_and_submit_payment_with_receipt(&requirement())
+            .await
+            .unwrap();
+        assert_eq!(receipt.payer, "0xtestnet");
+    }
+
+    #[test]
+    fn test_sync_policy_default_is_always() {
+        assert_eq!(SyncPolicy::default(), SyncPolicy::Always);
+    }
+
+    #[test]
+    fn test_sync_policy_should_sync() {
+        use std::time::Duration;
+
+        assert!(SyncPolicy::Always.should_sync(None));
+        assert!(SyncPolicy::Always.should_sync(Some(Duration::ZERO)));
+
+        assert!(!SyncPolicy::Never.should_sync(None));
+        assert!(!SyncPolicy::Never.should_sync(Some(Duration::from_secs(3600))));
+
+        let policy = SyncPolicy::IfOlderThan(Duration::from_secs(30));
+        assert!(policy.should_sync(None));
+        assert!(!policy.should_sync(Some(Duration::from_secs(10))));
+        assert!(policy.should_sync(Some(Duration::from_secs(30))));
+        assert!(policy.should_sync(Some(Duration::from_secs(60))));
+    }
 }
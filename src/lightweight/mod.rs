@@ -63,8 +63,15 @@ pub mod verification;
 
 #[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "client")]
+pub mod pool;
+#[cfg(feature = "client")]
+pub mod spending;
+
+#[cfg(test)]
+mod test_fixtures;
 
-pub use chain_state::{CachedBlockHeader, FacilitatorChainState};
+pub use chain_state::{CachedBlockHeader, DEFAULT_MAX_HEADER_FIELD_BYTES, FacilitatorChainState};
 pub use server::*;
 pub use types::*;
 
@@ -78,3 +85,7 @@ pub use verification::verify_lightweight_payment as verify_lightweight_payment_f
 
 #[cfg(feature = "client")]
 pub use client::*;
+#[cfg(feature = "client")]
+pub use pool::{NetworkPayers, PayerPool};
+#[cfg(feature = "client")]
+pub use spending::*;
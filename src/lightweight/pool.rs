@@ -0,0 +1,477 @@
+//! Combining payers for several accounts.
+//!
+//! A Miden account executes its transactions one after another, so a single
+//! `LightweightMidenPayer` serializes every payment behind its client lock.
+//! An agent making many concurrent paid requests can fund several accounts
+//! and pool their payers with [`PayerPool`]: each payment is handed to the
+//! least busy payer, in round-robin order on ties, and retries of a
+//! requirement go back to the payer that first took it.
+//!
+//! An agent with accounts on several networks can route each payment to the
+//! payer for the requirement's network with [`NetworkPayers`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use x402_chain_miden::lightweight::PayerPool;
+//!
+//! let pool = PayerPool::new(vec![
+//!     Arc::new(LightweightMidenPayer::new(account_a, client_a)),
+//!     Arc::new(LightweightMidenPayer::new(account_b, client_b)),
+//! ])
+//! .expect("at least one payer");
+//! let (header, receipt) = pool.create_and_submit_payment_with_receipt(&requirement).await?;
+//! // `receipt.payer` is the account that actually paid.
+//! ```
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use x402_types::chain::ChainId;
+
+use super::client::{LightweightPayerLike, PayerError, PaymentCache};
+use super::types::{LightweightPaymentHeader, LightweightPaymentRequirement, PaymentReceipt};
+
+/// A set of payers that share the payments made through it.
+///
+/// A requirement stays with the payer it was first routed to until it is
+/// [settled](Self::mark_settled), so a retry reaches the payer whose payment
+/// cache and pending payments know it, rather than paying again from another
+/// account. Spending guardrails stay per payer unless the payers share them.
+pub struct PayerPool {
+    payers: Vec<Arc<dyn LightweightPayerLike>>,
+    in_flight: Vec<AtomicUsize>,
+    next: AtomicUsize,
+    /// The payer each unsettled requirement was routed to, keyed like
+    /// [`PaymentCache`].
+    assigned: Mutex<HashMap<String, usize>>,
+}
+
+/// Marks a payer as busy until dropped.
+struct Lease<'a> {
+    pool: &'a PayerPool,
+    index: usize,
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        self.pool.in_flight[self.index].fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl PayerPool {
+    /// Creates a pool of `payers`, or returns `None` if `payers` is empty.
+    pub fn new(payers: Vec<Arc<dyn LightweightPayerLike>>) -> Option<Self> {
+        if payers.is_empty() {
+            return None;
+        }
+        let in_flight = payers.iter().map(|_| AtomicUsize::new(0)).collect();
+        Some(Self {
+            payers,
+            in_flight,
+            next: AtomicUsize::new(0),
+            assigned: Default::default(),
+        })
+    }
+
+    /// Returns the number of payers in the pool; always at least one.
+    pub fn payer_count(&self) -> usize {
+        self.payers.len()
+    }
+
+    /// Returns the number of payments currently in flight per payer.
+    pub fn in_flight(&self) -> Vec<usize> {
+        self.in_flight
+            .iter()
+            .map(|count| count.load(Ordering::SeqCst))
+            .collect()
+    }
+
+    /// Forgets which payer paid `requirement` once the resource server has
+    /// accepted the payment, so the pool stops routing it.
+    pub fn mark_settled(&self, requirement: &LightweightPaymentRequirement) {
+        self.assigned().remove(&PaymentCache::key(requirement));
+    }
+
+    /// Checks out the payer for `requirement`: the one it was already routed
+    /// to, or else the least busy payer, starting the search at the next
+    /// payer in round-robin order.
+    fn checkout(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> (Lease<'_>, &Arc<dyn LightweightPayerLike>) {
+        let index = match self.assigned().entry(PaymentCache::key(requirement)) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let start = self.next.fetch_add(1, Ordering::SeqCst) % self.payers.len();
+                let index = (0..self.payers.len())
+                    .map(|offset| (start + offset) % self.payers.len())
+                    .min_by_key(|&i| self.in_flight[i].load(Ordering::SeqCst))
+                    .unwrap_or(start);
+                *entry.insert(index)
+            }
+        };
+        self.in_flight[index].fetch_add(1, Ordering::SeqCst);
+        (Lease { pool: self, index }, &self.payers[index])
+    }
+
+    /// A poisoned lock is still used: losing an assignment could pay twice.
+    fn assigned(&self) -> std::sync::MutexGuard<'_, HashMap<String, usize>> {
+        self.assigned.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for PayerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayerPool")
+            .field(
+                "accounts",
+                &self
+                    .payers
+                    .iter()
+                    .map(|p| p.account_id())
+                    .collect::<Vec<_>>(),
+            )
+            .field("in_flight", &self.in_flight())
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl LightweightPayerLike for PayerPool {
+    /// Returns the first payer's account ID.
+    ///
+    /// Payments may come from any account in the pool; use
+    /// [`create_and_submit_payment_with_receipt`](LightweightPayerLike::create_and_submit_payment_with_receipt)
+    /// to learn which account paid.
+    fn account_id(&self) -> String {
+        self.payers[0].account_id()
+    }
+
+    /// Returns the first payer's account ID for `network`.
+    fn account_id_for(&self, network: &ChainId) -> Option<String> {
+        self.payers[0].account_id_for(network)
+    }
+
+    async fn create_and_submit_payment(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error> {
+        let (_lease, payer) = self.checkout(requirement);
+        payer.create_and_submit_payment(requirement).await
+    }
+
+    async fn create_and_submit_payment_with_receipt(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<(LightweightPaymentHeader, PaymentReceipt), x402_types::scheme::client::X402Error>
+    {
+        let (_lease, payer) = self.checkout(requirement);
+        payer
+            .create_and_submit_payment_with_receipt(requirement)
+            .await
+    }
+}
+
+/// Payers keyed by network, each paying the requirements for its network.
+///
+/// Requirements for a network without a payer are refused with
+/// [`PayerError::UnsupportedNetwork`].
+#[derive(Default)]
+pub struct NetworkPayers {
+    payers: HashMap<ChainId, Arc<dyn LightweightPayerLike>>,
+}
+
+impl NetworkPayers {
+    /// Creates an empty set of payers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pays requirements for `network` with `payer`, replacing any previous
+    /// payer for that network.
+    pub fn with_payer(mut self, network: ChainId, payer: Arc<dyn LightweightPayerLike>) -> Self {
+        self.payers.insert(network, payer);
+        self
+    }
+
+    /// Returns the payer for `network`, if any.
+    pub fn payer_for(&self, network: &ChainId) -> Option<&Arc<dyn LightweightPayerLike>> {
+        self.payers.get(network)
+    }
+
+    fn route(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<&Arc<dyn LightweightPayerLike>, PayerError> {
+        self.payer_for(&requirement.network)
+            .ok_or_else(|| PayerError::UnsupportedNetwork(requirement.network.to_string()))
+    }
+}
+
+impl std::fmt::Debug for NetworkPayers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.payers
+                    .iter()
+                    .map(|(network, payer)| (network.to_string(), payer.account_id_for(network))),
+            )
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl LightweightPayerLike for NetworkPayers {
+    /// Returns the account ID of the payer for the first network, in
+    /// CAIP-2 order, or an empty string if there is no payer.
+    ///
+    /// Each network may be paid from a different account; use
+    /// [`account_id_for`](LightweightPayerLike::account_id_for) for the
+    /// account that pays on a given network.
+    fn account_id(&self) -> String {
+        self.payers
+            .iter()
+            .min_by_key(|(network, _)| network.to_string())
+            .and_then(|(network, payer)| payer.account_id_for(network))
+            .unwrap_or_default()
+    }
+
+    /// Returns the account ID of the payer for `network`, if any.
+    fn account_id_for(&self, network: &ChainId) -> Option<String> {
+        self.payer_for(network)?.account_id_for(network)
+    }
+
+    async fn create_and_submit_payment(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error> {
+        self.route(requirement)?
+            .create_and_submit_payment(requirement)
+            .await
+    }
+
+    async fn create_and_submit_payment_with_receipt(
+        &self,
+        requirement: &LightweightPaymentRequirement,
+    ) -> Result<(LightweightPaymentHeader, PaymentReceipt), x402_types::scheme::client::X402Error>
+    {
+        self.route(requirement)?
+            .create_and_submit_payment_with_receipt(requirement)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lightweight::test_fixtures::{MockPayer, requirement};
+    use std::sync::Mutex;
+
+    fn pool_of(payers: &[Arc<MockPayer>]) -> PayerPool {
+        PayerPool::new(
+            payers
+                .iter()
+                .map(|p| p.clone() as Arc<dyn LightweightPayerLike>)
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    /// A distinct requirement per `n`, as issued for separate requests.
+    fn nth_requirement(n: usize) -> LightweightPaymentRequirement {
+        LightweightPaymentRequirement {
+            recipient_digest: format!("0xdigest{n}"),
+            ..requirement()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sequential_payments_round_robin() {
+        let payers = [MockPayer::new("0xa"), MockPayer::new("0xb")];
+        let pool = pool_of(&payers);
+
+        let mut used = Vec::new();
+        for n in 0..4 {
+            let (_, receipt) = pool
+                .create_and_submit_payment_with_receipt(&nth_requirement(n))
+                .await
+                .unwrap();
+            used.push(receipt.payer);
+        }
+        assert_eq!(used, ["0xa", "0xb", "0xa", "0xb"]);
+        assert_eq!(pool.in_flight(), [0, 0]);
+    }
+
+    #[test]
+    fn test_checkout_prefers_idle_payer() {
+        let payers = [MockPayer::new("0xa"), MockPayer::new("0xb")];
+        let pool = pool_of(&payers);
+
+        let (first, payer) = pool.checkout(&nth_requirement(0));
+        assert_eq!(payer.account_id(), "0xa");
+        let (second, payer) = pool.checkout(&nth_requirement(1));
+        assert_eq!(payer.account_id(), "0xb");
+        drop(second);
+
+        // Round robin would pick 0xa next, but it is still busy.
+        let (_third, payer) = pool.checkout(&nth_requirement(2));
+        assert_eq!(payer.account_id(), "0xb");
+        assert_eq!(pool.in_flight(), [1, 1]);
+
+        drop(first);
+        assert_eq!(pool.in_flight(), [0, 1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_payments_spread_across_payers() {
+        let payers = [MockPayer::new("0xa"), MockPayer::new("0xb")];
+        let pool = Arc::new(pool_of(&payers));
+        let receipts = Arc::new(Mutex::new(Vec::new()));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|n| {
+                let pool = pool.clone();
+                let receipts = receipts.clone();
+                tokio::spawn(async move {
+                    let (_, receipt) = pool
+                        .create_and_submit_payment_with_receipt(&nth_requirement(n))
+                        .await
+                        .unwrap();
+                    receipts.lock().unwrap().push(receipt.payer);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let receipts = receipts.lock().unwrap();
+        assert_eq!(receipts.len(), 8);
+        for payer in &payers {
+            assert!(payer.calls.load(Ordering::SeqCst) > 0);
+            assert_eq!(
+                receipts.iter().filter(|p| **p == payer.account).count(),
+                payer.calls.load(Ordering::SeqCst)
+            );
+        }
+        assert_eq!(pool.in_flight(), [0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_payment_releases_payer() {
+        let failing = Arc::new(MockPayer {
+            account: "0xa".to_string(),
+            fail: true,
+            ..Default::default()
+        });
+        let pool = pool_of(&[failing]);
+
+        assert!(
+            pool.create_and_submit_payment(&requirement())
+                .await
+                .is_err()
+        );
+        assert_eq!(pool.in_flight(), [0]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_reaches_the_same_payer() {
+        let failing = Arc::new(MockPayer {
+            account: "0xa".to_string(),
+            fail: true,
+            ..Default::default()
+        });
+        let idle = MockPayer::new("0xb");
+        let pool = pool_of(&[failing.clone(), idle.clone()]);
+
+        // The retry goes back to the payer that may already have submitted
+        // the payment, even though the other payer is idle and next in turn.
+        for _ in 0..2 {
+            assert!(
+                pool.create_and_submit_payment(&requirement())
+                    .await
+                    .is_err()
+            );
+        }
+        assert_eq!(failing.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(idle.calls.load(Ordering::SeqCst), 0);
+
+        // Once settled, the requirement is routed afresh.
+        pool.mark_settled(&requirement());
+        pool.create_and_submit_payment(&requirement())
+            .await
+            .unwrap();
+        assert_eq!(idle.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_network_payers_route_by_network() {
+        let testnet_payer = MockPayer::new("0xa");
+        let mainnet_payer = MockPayer::new("0xb");
+        let testnet = ChainId::new("miden", "testnet");
+        let mainnet = ChainId::new("miden", "mainnet");
+        let payers = NetworkPayers::new()
+            .with_payer(testnet.clone(), testnet_payer.clone())
+            .with_payer(mainnet.clone(), mainnet_payer.clone());
+
+        assert_eq!(payers.account_id_for(&testnet).as_deref(), Some("0xa"));
+        assert_eq!(payers.account_id_for(&mainnet).as_deref(), Some("0xb"));
+        assert_eq!(payers.account_id(), "0xb");
+
+        // A pool over network payers reports the account for each network.
+        let payers = Arc::new(payers);
+        let pool = PayerPool::new(vec![payers.clone() as Arc<dyn LightweightPayerLike>]).unwrap();
+        assert_eq!(pool.account_id_for(&testnet).as_deref(), Some("0xa"));
+        assert_eq!(pool.account_id_for(&mainnet).as_deref(), Some("0xb"));
+
+        let mut mainnet_requirement = requirement();
+        mainnet_requirement.network = mainnet;
+        let (_, receipt) = payers
+            .create_and_submit_payment_with_receipt(&mainnet_requirement)
+            .await
+            .unwrap();
+        assert_eq!(receipt.payer, "0xb");
+        let (_, receipt) = payers
+            .create_and_submit_payment_with_receipt(&requirement())
+            .await
+            .unwrap();
+        assert_eq!(receipt.payer, "0xa");
+
+        assert_eq!(testnet_payer.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(mainnet_payer.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_network_payers_refuse_unknown_network() {
+        let payer = MockPayer::new("0xa");
+        let payers =
+            NetworkPayers::new().with_payer(ChainId::new("miden", "mainnet"), payer.clone());
+
+        let err = payers
+            .create_and_submit_payment(&requirement())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No payer account configured for network miden:testnet"),
+            "{err}"
+        );
+        assert_eq!(
+            payers.account_id_for(&ChainId::new("miden", "testnet")),
+            None
+        );
+        assert_eq!(payer.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_empty_pool_is_refused() {
+        assert!(PayerPool::new(Vec::new()).is_none());
+
+        let payers = [MockPayer::new("0xa"), MockPayer::new("0xb")];
+        assert_eq!(pool_of(&payers).payer_count(), 2);
+    }
+}
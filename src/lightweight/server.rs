@@ -57,7 +57,9 @@ use super::types::{LightweightPaymentRequirement, PaymentContext};
 /// 1. Generates a random `serial_num` for this payment request
 /// 2. Computes `recipient_digest` from the serial number, P2ID script root,
 ///    and the recipient's account ID
-/// 3. Returns both the requirement (to send to the agent) and the context
+/// 3. Derives the note tag from the recipient's account ID (see
+///    [`p2id_note_tag`])
+/// 4. Returns both the requirement (to send to the agent) and the context
 ///    (to store server-side for later verification)
 ///
 /// # Parameters
@@ -65,29 +67,71 @@ use super::types::{LightweightPaymentRequirement, PaymentContext};
 /// - `pay_to`: The recipient's Miden account ID (hex-encoded)
 /// - `asset_faucet_id`: The faucet account ID (hex-encoded) for the token
 /// - `amount`: The required payment amount in the token's smallest unit
-/// - `note_tag`: The note tag for efficient filtering during `sync_state()`
 /// - `network`: The CAIP-2 network identifier (e.g., `miden:testnet`)
 ///
 /// # Feature gating
 ///
 /// With `miden-native`: computes a real RPO recipient_digest using Miden
-/// crypto primitives.
+/// crypto primitives, and rejects an `asset_faucet_id` that is not a
+/// fungible faucet.
 /// Without `miden-native`: uses a non-cryptographic placeholder digest
 /// (suitable for testing only).
 pub fn create_payment_requirement(
     pay_to: &str,
     asset_faucet_id: &str,
     amount: u64,
-    note_tag: u32,
     network: x402_types::chain::ChainId,
 ) -> Result<(LightweightPaymentRequirement, PaymentContext), String> {
-    // Generate a random serial number for this payment request.
-    // In production this should use a CSPRNG; for now we use a simple
-    // approach that works across feature gates.
-    let serial_num_hex = generate_serial_num_hex();
+    create_payment_requirement_from_serial_num(
+        pay_to,
+        asset_faucet_id,
+        amount,
+        network,
+        generate_serial_num(),
+    )
+}
+
+/// Creates a lightweight payment requirement with a caller-chosen serial number.
+///
+/// Same as [`create_payment_requirement`], but uses `serial_num` instead of
+/// a fresh random one, so the same inputs always produce the same
+/// `recipient_digest` and note ID. This is meant for reproducible tests and
+/// golden vectors.
+///
+/// **Never use this in production.** The serial number is what makes each
+/// requirement unique: reusing one lets an agent satisfy a new requirement
+/// with a note it already paid, and a predictable one lets anyone compute
+/// the note's nullifier. It is only available in tests and with the
+/// `test-utils` feature.
+#[cfg(any(test, feature = "test-utils"))]
+#[doc(hidden)]
+pub fn create_payment_requirement_with_serial_num(
+    pay_to: &str,
+    asset_faucet_id: &str,
+    amount: u64,
+    network: x402_types::chain::ChainId,
+    serial_num: [u8; 32],
+) -> Result<(LightweightPaymentRequirement, PaymentContext), String> {
+    create_payment_requirement_from_serial_num(pay_to, asset_faucet_id, amount, network, serial_num)
+}
+
+fn create_payment_requirement_from_serial_num(
+    pay_to: &str,
+    asset_faucet_id: &str,
+    amount: u64,
+    network: x402_types::chain::ChainId,
+    serial_num: [u8; 32],
+) -> Result<(LightweightPaymentRequirement, PaymentContext), String> {
+    // Reject non-faucet assets before handing out a requirement that can
+    // never be paid.
+    #[cfg(feature = "miden-native")]
+    super::types::parse_fungible_faucet_id(asset_faucet_id).map_err(|e| e.to_string())?;
+
+    let serial_num_hex = format!("0x{}", hex::encode(serial_num));
 
     // Compute recipient_digest (feature-gated)
     let recipient_digest = compute_recipient_digest(pay_to, &serial_num_hex)?;
+    let note_tag = p2id_note_tag(pay_to)?;
 
     let requirement = LightweightPaymentRequirement {
         recipient_digest: recipient_digest.clone(),
@@ -114,13 +158,53 @@ pub fn create_payment_requirement(
     Ok((requirement, context))
 }
 
-/// Generates a hex-encoded random serial number (32 bytes).
+/// Generates a random serial number (32 bytes).
 ///
 /// Uses the `getrandom` crate to obtain cryptographically secure random bytes.
-fn generate_serial_num_hex() -> String {
+fn generate_serial_num() -> [u8; 32] {
     let mut bytes = [0u8; 32];
     getrandom::getrandom(&mut bytes).expect("Failed to generate random bytes");
-    format!("0x{}", hex::encode(bytes))
+    bytes
+}
+
+/// Derives the note tag of a P2ID note paying `pay_to`.
+///
+/// This is the tag the recipient's client tracks during `sync_state()`, so a
+/// note carrying any other tag is never discovered by the recipient. It
+/// matches `NoteTag::with_account_target`:
+///
+/// ```text
+/// network accounts:           [00 | top 30 bits of prefix]
+/// public / private accounts:  [00 | top 14 bits of prefix | 16 zero bits]
+/// ```
+#[cfg(feature = "miden-native")]
+pub fn p2id_note_tag(pay_to: &str) -> Result<u32, String> {
+    use miden_protocol::account::AccountId;
+    use miden_protocol::note::NoteTag;
+
+    let target = AccountId::from_hex(pay_to)
+        .map_err(|e| format!("pay_to is not a valid Miden account ID: {e}"))?;
+    Ok(NoteTag::with_account_target(target).as_u32())
+}
+
+/// Derives the note tag of a P2ID note paying `pay_to` (no miden-native).
+///
+/// Same bit layout as the miden-native version, computed from the raw
+/// account ID bytes.
+#[cfg(not(feature = "miden-native"))]
+pub fn p2id_note_tag(pay_to: &str) -> Result<u32, String> {
+    use crate::chain::{MidenAccountAddress, MidenStorageMode};
+
+    let target: MidenAccountAddress = pay_to
+        .parse()
+        .map_err(|e| format!("pay_to is not a valid Miden account ID: {e}"))?;
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&target.as_bytes()[..8]);
+    let high_bits = (u64::from_be_bytes(prefix) >> 34) as u32;
+    match target.storage_mode() {
+        Some(MidenStorageMode::Network) => Ok(high_bits),
+        _ => Ok(high_bits & (u32::MAX << 16)),
+    }
 }
 
 /// Computes the recipient digest using real RPO256 hashing (miden-native).
@@ -264,12 +348,15 @@ fn verify_note_id_and_proof(
         note_id: header.note_id.clone(),
         block_num: header.block_num,
         error: None,
+        details: None,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lightweight::test_fixtures::{FAUCET, PAY_TO};
+    use crate::lightweight::types::LIGHTWEIGHT_PAYLOAD_VERSION;
 
     fn make_context() -> PaymentContext {
         PaymentContext::new(
@@ -283,6 +370,7 @@ mod tests {
 
     fn make_header() -> LightweightPaymentHeader {
         LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xdeadbeefcafebabe1234567890abcdef".to_string(),
             block_num: 42,
             note_index: 0,
@@ -308,6 +396,7 @@ mod tests {
     fn test_verify_rejects_empty_note_id() {
         let context = make_context();
         let header = LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: String::new(),
             block_num: 42,
             note_index: 0,
@@ -322,6 +411,7 @@ mod tests {
     fn test_verify_rejects_empty_inclusion_proof() {
         let context = make_context();
         let header = LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xnote".to_string(),
             block_num: 42,
             note_index: 0,
@@ -336,6 +426,7 @@ mod tests {
     fn test_verify_rejects_zero_block_num() {
         let context = make_context();
         let header = LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xnote".to_string(),
             block_num: 0,
             note_index: 0,
@@ -358,4 +449,67 @@ mod tests {
         assert_eq!(response.block_num, header.block_num);
         assert!(response.error.is_none());
     }
+
+    fn requirement_with_serial_num(serial_num: [u8; 32]) -> LightweightPaymentRequirement {
+        create_payment_requirement_with_serial_num(
+            PAY_TO,
+            FAUCET,
+            1_000_000,
+            x402_types::chain::ChainId::new("miden", "testnet"),
+            serial_num,
+        )
+        .unwrap()
+        .0
+    }
+
+    #[test]
+    fn test_fixed_serial_num_is_reproducible() {
+        let first = requirement_with_serial_num([7u8; 32]);
+        let second = requirement_with_serial_num([7u8; 32]);
+        assert_eq!(first.recipient_digest, second.recipient_digest);
+        assert_eq!(
+            first.serial_num.as_deref(),
+            Some("0x0707070707070707070707070707070707070707070707070707070707070707")
+        );
+
+        let other = requirement_with_serial_num([8u8; 32]);
+        assert_ne!(first.recipient_digest, other.recipient_digest);
+    }
+
+    #[test]
+    fn test_random_serial_nums_differ() {
+        let network = x402_types::chain::ChainId::new("miden", "testnet");
+        let (first, _) =
+            create_payment_requirement(PAY_TO, FAUCET, 1_000_000, network.clone()).unwrap();
+        let (second, _) = create_payment_requirement(PAY_TO, FAUCET, 1_000_000, network).unwrap();
+        assert_ne!(first.serial_num, second.serial_num);
+        assert_ne!(first.recipient_digest, second.recipient_digest);
+    }
+
+    #[test]
+    fn test_note_tag_is_derived_from_pay_to() {
+        let (requirement, context) = create_payment_requirement(
+            PAY_TO,
+            FAUCET,
+            1_000_000,
+            x402_types::chain::ChainId::new("miden", "testnet"),
+        )
+        .unwrap();
+        // Public account: the top 14 bits of the prefix after two zero bits.
+        assert_eq!(requirement.note_tag, 0x2aae_0000);
+        assert_eq!(context.note_tag, requirement.note_tag);
+    }
+
+    #[test]
+    fn test_network_account_note_tag_keeps_30_bits() {
+        assert_eq!(
+            p2id_note_tag("0xaabbccddeeff00502233aabbccddee"),
+            Ok(0x2aae_f337)
+        );
+    }
+
+    #[test]
+    fn test_note_tag_rejects_invalid_pay_to() {
+        assert!(p2id_note_tag("0xnotanaccount").is_err());
+    }
 }
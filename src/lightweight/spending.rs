@@ -0,0 +1,819 @@
+//! Client-side spending guardrails for lightweight payments.
+//!
+//! An autonomous agent pays whatever a resource server asks for, so a
+//! malicious or buggy server could quote an absurd price. The types in this
+//! module let the payer refuse such payments before any transaction is
+//! executed or proved: [`AssetAllowlist`] restricts which faucets may be
+//! spent, [`SpendingLimits`] caps each payment, and a [`SpendingBudget`]
+//! caps cumulative spending over time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use x402_chain_miden::lightweight::SpendingLimits;
+//!
+//! let limits = SpendingLimits::new()
+//!     .with_global_max_amount(10_000_000)
+//!     .with_max_amount(usdc_faucet, 1_000_000);
+//! let payer = LightweightMidenPayer::new(account_id, client).with_spending_limits(limits);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use x402_types::chain::ChainId;
+
+use super::types::LightweightPaymentRequirement;
+use crate::chain::{
+    MidenAccountAddress, MidenChainReference, MidenTokenRegistry, MidenTokenRegistryError,
+};
+
+/// Errors returned when a payment is refused by a spending guardrail.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SpendingError {
+    /// The requested amount is above the configured per-payment limit.
+    #[error("Amount {requested} exceeds the per-payment limit of {limit}")]
+    AmountExceedsLimit { requested: u64, limit: u64 },
+
+    /// The requested asset is not on the payer's allowlist.
+    #[error("Asset {asset} is not allowed on network {network}")]
+    AssetNotAllowed { asset: String, network: String },
+
+    /// The asset is not a valid faucet ID, so no configured budget limit
+    /// can be applied to it.
+    #[error("Asset {asset} is not a valid faucet ID")]
+    UnrecognizedAsset { asset: String },
+
+    /// The payment would exceed the spending budget for the current window.
+    #[error(
+        "Spending budget exceeded: requested {requested}, {remaining} remaining until {resets_at} (Unix seconds)"
+    )]
+    BudgetExceeded {
+        requested: u64,
+        remaining: u64,
+        resets_at: u64,
+    },
+}
+
+impl From<SpendingError> for x402_types::scheme::client::X402Error {
+    fn from(value: SpendingError) -> Self {
+        x402_types::scheme::client::X402Error::SigningError(value.to_string())
+    }
+}
+
+/// Per-payment amount caps, globally and per faucet.
+///
+/// Amounts are in the token's smallest unit. When both a global and a
+/// faucet-specific cap apply, the lower one wins.
+#[derive(Debug, Clone, Default)]
+pub struct SpendingLimits {
+    global_max_amount: Option<u64>,
+    per_faucet_max_amount: HashMap<MidenAccountAddress, u64>,
+}
+
+impl SpendingLimits {
+    /// Creates limits that allow any amount.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps every payment, whatever its faucet, at `max_base_units`.
+    pub fn with_global_max_amount(mut self, max_base_units: u64) -> Self {
+        self.global_max_amount = Some(max_base_units);
+        self
+    }
+
+    /// Caps payments in `faucet` at `max_base_units`.
+    pub fn with_max_amount(mut self, faucet: MidenAccountAddress, max_base_units: u64) -> Self {
+        self.per_faucet_max_amount.insert(faucet, max_base_units);
+        self
+    }
+
+    /// Caps payments in the token registered as `symbol` on `network` at a
+    /// whole-token amount such as `"5.00"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenTokenRegistryError::UnknownToken`] if no such token is
+    /// registered, and [`MidenTokenRegistryError::Amount`] if `max` does not
+    /// parse.
+    pub fn with_registered_max_amount(
+        self,
+        registry: &MidenTokenRegistry,
+        network: &MidenChainReference,
+        symbol: &str,
+        max: &str,
+    ) -> Result<Self, MidenTokenRegistryError> {
+        let max = registry.parse_amount(network, symbol, max)?;
+        Ok(self.with_max_amount(max.token.faucet_id, max.amount))
+    }
+
+    /// Returns the cap that applies to payments in `asset`, if any.
+    ///
+    /// `asset` is the hex faucet ID from the payment requirement. An asset
+    /// that is not a valid account ID only falls under the global cap.
+    pub fn limit_for(&self, asset: &str) -> Option<u64> {
+        let faucet_limit = asset
+            .parse::<MidenAccountAddress>()
+            .ok()
+            .and_then(|faucet| self.per_faucet_max_amount.get(&faucet).copied());
+        match (self.global_max_amount, faucet_limit) {
+            (Some(global), Some(faucet)) => Some(global.min(faucet)),
+            (global, faucet) => global.or(faucet),
+        }
+    }
+
+    /// Checks a requirement against the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpendingError::AmountExceedsLimit`] if the requested amount
+    /// is above the applicable cap.
+    pub fn check(&self, requirement: &LightweightPaymentRequirement) -> Result<(), SpendingError> {
+        match self.limit_for(&requirement.asset) {
+            Some(limit) if requirement.amount > limit => Err(SpendingError::AmountExceedsLimit {
+                requested: requirement.amount,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Faucets the payer is willing to spend, globally and per network.
+///
+/// An empty allowlist allows every asset. Once any faucet is added, a
+/// requirement is only accepted if its asset is allowed on every network or
+/// on the requirement's network.
+#[derive(Debug, Clone, Default)]
+pub struct AssetAllowlist {
+    global: Vec<MidenAccountAddress>,
+    per_network: HashMap<ChainId, Vec<MidenAccountAddress>>,
+}
+
+impl AssetAllowlist {
+    /// Creates an allowlist that allows every asset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows `faucets` on every network.
+    pub fn with_assets(mut self, faucets: impl IntoIterator<Item = MidenAccountAddress>) -> Self {
+        self.global.extend(faucets);
+        self
+    }
+
+    /// Allows `faucets` on `network` only.
+    pub fn with_network_assets(
+        mut self,
+        network: ChainId,
+        faucets: impl IntoIterator<Item = MidenAccountAddress>,
+    ) -> Self {
+        self.per_network.entry(network).or_default().extend(faucets);
+        self
+    }
+
+    /// Returns `true` if no faucet has been added.
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.per_network.values().all(Vec::is_empty)
+    }
+
+    /// Returns `true` if `asset` (hex faucet ID) may be spent on `network`.
+    pub fn allows(&self, network: &ChainId, asset: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Ok(faucet) = asset.parse::<MidenAccountAddress>() else {
+            return false;
+        };
+        self.global.contains(&faucet)
+            || self
+                .per_network
+                .get(network)
+                .is_some_and(|faucets| faucets.contains(&faucet))
+    }
+
+    /// Checks a requirement against the allowlist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpendingError::AssetNotAllowed`] if the requirement's asset
+    /// is not allowed on its network.
+    pub fn check(&self, requirement: &LightweightPaymentRequirement) -> Result<(), SpendingError> {
+        if self.allows(&requirement.network, &requirement.asset) {
+            Ok(())
+        } else {
+            Err(SpendingError::AssetNotAllowed {
+                asset: requirement.asset.clone(),
+                network: requirement.network.to_string(),
+            })
+        }
+    }
+}
+
+// ============================================================================
+// Cumulative spending budget
+// ============================================================================
+
+/// Tracks cumulative spending across payments.
+///
+/// A payment first [`reserve`](Self::reserve)s its amount, which counts
+/// against the budget straight away, so payers sharing one budget cannot
+/// both pass the check for the last of the allowance. Once the payment has
+/// been submitted the payer [`commit`](Self::commit)s the reservation; if
+/// it fails before that, the payer [`release`](Self::release)s it.
+/// [`SpendingReservation`] does both for a shared budget. Implementations
+/// may persist records; [`InMemorySpendingBudget`] keeps them in memory.
+pub trait SpendingBudget: Send + Sync + std::fmt::Debug {
+    /// Reserves `amount` of `asset` (hex faucet ID) if it may be spent now.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpendingError::BudgetExceeded`] if the payment would exceed
+    /// the budget, and [`SpendingError::UnrecognizedAsset`] if `asset` is not
+    /// a valid faucet ID while any limit is configured.
+    fn reserve(&self, asset: &str, amount: u64) -> Result<ReservationId, SpendingError>;
+
+    /// Records the reserved payment as spent.
+    fn commit(&self, reservation: ReservationId);
+
+    /// Returns the reserved amount to the budget.
+    fn release(&self, reservation: ReservationId);
+}
+
+/// Identifies a reservation made with [`SpendingBudget::reserve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReservationId(pub u64);
+
+/// A reservation on a shared [`SpendingBudget`] that is released when
+/// dropped without being committed.
+///
+/// This keeps the budget correct when a payment fails or its future is
+/// dropped part-way through.
+#[derive(Debug)]
+#[must_use = "dropping a reservation releases it"]
+pub struct SpendingReservation {
+    budget: Arc<dyn SpendingBudget>,
+    id: Option<ReservationId>,
+}
+
+impl SpendingReservation {
+    /// Reserves `amount` of `asset` on `budget`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`SpendingBudget::reserve`].
+    pub fn new(
+        budget: Arc<dyn SpendingBudget>,
+        asset: &str,
+        amount: u64,
+    ) -> Result<Self, SpendingError> {
+        let id = budget.reserve(asset, amount)?;
+        Ok(Self {
+            budget,
+            id: Some(id),
+        })
+    }
+
+    /// Records the reserved payment as spent.
+    pub fn commit(mut self) {
+        if let Some(id) = self.id.take() {
+            self.budget.commit(id);
+        }
+    }
+}
+
+impl Drop for SpendingReservation {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.budget.release(id);
+        }
+    }
+}
+
+/// Source of the current time for [`InMemorySpendingBudget`].
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// [`Clock`] backed by the system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// In-memory sliding-window spending budget, per faucet.
+///
+/// For example, "at most 50 USDC per hour":
+///
+/// ```ignore
+/// let budget = InMemorySpendingBudget::new(Duration::from_secs(3600))
+///     .with_limit(usdc_faucet, 50_000_000);
+/// ```
+///
+/// Faucets without a limit are not restricted, but once any limit is set an
+/// asset that is not a valid faucet ID is refused. Records are lost when the
+/// process exits.
+#[derive(Debug)]
+pub struct InMemorySpendingBudget {
+    window: Duration,
+    limits: HashMap<MidenAccountAddress, u64>,
+    ledger: Mutex<Ledger>,
+    clock: Arc<dyn Clock>,
+}
+
+/// Committed payments and outstanding reservations of an
+/// [`InMemorySpendingBudget`].
+#[derive(Debug, Default)]
+struct Ledger {
+    records: VecDeque<(SystemTime, MidenAccountAddress, u64)>,
+    reserved: HashMap<ReservationId, (MidenAccountAddress, u64)>,
+    next_id: u64,
+}
+
+impl InMemorySpendingBudget {
+    /// Creates a budget with a sliding window of length `window`.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            limits: HashMap::new(),
+            ledger: Mutex::new(Ledger::default()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Allows at most `max_base_units` of `faucet` per window.
+    pub fn with_limit(mut self, faucet: MidenAccountAddress, max_base_units: u64) -> Self {
+        self.limits.insert(faucet, max_base_units);
+        self
+    }
+
+    /// Uses `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Drops records that have left the window.
+    fn prune(
+        &self,
+        records: &mut VecDeque<(SystemTime, MidenAccountAddress, u64)>,
+        now: SystemTime,
+    ) {
+        while let Some((at, _, _)) = records.front() {
+            if *at + self.window > now {
+                break;
+            }
+            records.pop_front();
+        }
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl SpendingBudget for InMemorySpendingBudget {
+    fn reserve(&self, asset: &str, amount: u64) -> Result<ReservationId, SpendingError> {
+        let faucet = match asset.parse::<MidenAccountAddress>() {
+            Ok(faucet) => Some(faucet),
+            // An unparsable asset cannot be matched against the limits, so
+            // refuse it rather than let it bypass them.
+            Err(_) if !self.limits.is_empty() => {
+                return Err(SpendingError::UnrecognizedAsset {
+                    asset: asset.to_string(),
+                });
+            }
+            Err(_) => None,
+        };
+
+        let now = self.clock.now();
+        // The ledger stays consistent even if a holder of the lock panicked,
+        // so keep enforcing the budget rather than failing open.
+        let mut ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        self.prune(&mut ledger.records, now);
+
+        if let Some(&limit) = faucet.as_ref().and_then(|f| self.limits.get(f)) {
+            let faucet = faucet.as_ref();
+            let committed = ledger.records.iter().filter(|(_, f, _)| Some(f) == faucet);
+            let reserved: u64 = ledger
+                .reserved
+                .values()
+                .filter(|(f, _)| Some(f) == faucet)
+                .map(|(_, a)| *a)
+                .sum();
+            let spent = committed.clone().map(|(_, _, a)| *a).sum::<u64>() + reserved;
+            let remaining = limit.saturating_sub(spent);
+            if amount > remaining {
+                // Allowance starts coming back when the oldest payment leaves
+                // the window.
+                let resets_at = committed
+                    .map(|(at, _, _)| *at + self.window)
+                    .next()
+                    .unwrap_or(now);
+                return Err(SpendingError::BudgetExceeded {
+                    requested: amount,
+                    remaining,
+                    resets_at: unix_secs(resets_at),
+                });
+            }
+        }
+
+        let id = ReservationId(ledger.next_id);
+        ledger.next_id += 1;
+        if let Some(faucet) = faucet {
+            ledger.reserved.insert(id, (faucet, amount));
+        }
+        Ok(id)
+    }
+
+    fn commit(&self, reservation: ReservationId) {
+        let now = self.clock.now();
+        let mut ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        self.prune(&mut ledger.records, now);
+        if let Some((faucet, amount)) = ledger.reserved.remove(&reservation) {
+            ledger.records.push_back((now, faucet, amount));
+        }
+    }
+
+    fn release(&self, reservation: ReservationId) {
+        let mut ledger = self.ledger.lock().unwrap_or_else(PoisonError::into_inner);
+        ledger.reserved.remove(&reservation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lightweight::test_fixtures::{FAUCET as USDC, requirement_for};
+
+    const OTHER: &str = "0xaabbccddeeff00102233aabbccddee";
+
+    #[test]
+    fn test_no_limits_allow_anything() {
+        let limits = SpendingLimits::new();
+        assert!(limits.check(&requirement_for(USDC, u64::MAX)).is_ok());
+    }
+
+    #[test]
+    fn test_global_limit() {
+        let limits = SpendingLimits::new().with_global_max_amount(1_000);
+        assert!(limits.check(&requirement_for(USDC, 1_000)).is_ok());
+        assert_eq!(
+            limits.check(&requirement_for(OTHER, 1_001)),
+            Err(SpendingError::AmountExceedsLimit {
+                requested: 1_001,
+                limit: 1_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_faucet_specific_limits() {
+        let limits = SpendingLimits::new()
+            .with_max_amount(USDC.parse().unwrap(), 500)
+            .with_max_amount(OTHER.parse().unwrap(), 5_000);
+
+        assert!(limits.check(&requirement_for(USDC, 500)).is_ok());
+        assert!(limits.check(&requirement_for(USDC, 501)).is_err());
+        assert!(limits.check(&requirement_for(OTHER, 5_000)).is_ok());
+        assert!(limits.check(&requirement_for(OTHER, 5_001)).is_err());
+
+        // Faucet matching ignores hex case.
+        assert!(
+            limits
+                .check(&requirement_for(
+                    &USDC.to_uppercase().replace("0X", "0x"),
+                    501
+                ))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_registered_max_amount_in_whole_tokens() {
+        let mut registry = MidenTokenRegistry::new();
+        registry
+            .register(
+                crate::chain::MidenTokenDeployment::new(
+                    MidenChainReference::testnet(),
+                    USDC.parse().unwrap(),
+                    6,
+                )
+                .with_symbol("USDC"),
+            )
+            .unwrap();
+
+        let limits = SpendingLimits::new()
+            .with_registered_max_amount(&registry, &MidenChainReference::testnet(), "usdc", "2.5")
+            .unwrap();
+        assert_eq!(limits.limit_for(USDC), Some(2_500_000));
+        assert!(limits.check(&requirement_for(USDC, 2_500_001)).is_err());
+
+        assert!(matches!(
+            SpendingLimits::new().with_registered_max_amount(
+                &registry,
+                &MidenChainReference::mainnet(),
+                "USDC",
+                "1"
+            ),
+            Err(MidenTokenRegistryError::UnknownToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lower_of_global_and_faucet_limit_applies() {
+        let limits = SpendingLimits::new()
+            .with_global_max_amount(1_000)
+            .with_max_amount(USDC.parse().unwrap(), 5_000);
+        assert_eq!(limits.limit_for(USDC), Some(1_000));
+        assert_eq!(limits.limit_for(OTHER), Some(1_000));
+        assert_eq!(limits.limit_for("not-a-faucet"), Some(1_000));
+    }
+
+    #[test]
+    fn test_empty_allowlist_allows_everything() {
+        let allowlist = AssetAllowlist::new();
+        assert!(allowlist.is_empty());
+        assert!(allowlist.check(&requirement_for(USDC, 1)).is_ok());
+        assert!(allowlist.check(&requirement_for("not-a-faucet", 1)).is_ok());
+    }
+
+    #[test]
+    fn test_allowlist_filters_mixed_requirements() {
+        let allowlist = AssetAllowlist::new().with_assets([USDC.parse().unwrap()]);
+
+        assert!(allowlist.check(&requirement_for(USDC, 1)).is_ok());
+        assert_eq!(
+            allowlist.check(&requirement_for(OTHER, 1)),
+            Err(SpendingError::AssetNotAllowed {
+                asset: OTHER.to_string(),
+                network: "miden:testnet".to_string()
+            })
+        );
+        assert!(
+            allowlist
+                .check(&requirement_for("not-a-faucet", 1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_allowlist_per_network() {
+        let testnet = x402_types::chain::ChainId::new("miden", "testnet");
+        let mainnet = x402_types::chain::ChainId::new("miden", "mainnet");
+        let allowlist = AssetAllowlist::new()
+            .with_assets([USDC.parse().unwrap()])
+            .with_network_assets(testnet.clone(), [OTHER.parse().unwrap()]);
+
+        assert!(allowlist.allows(&testnet, USDC));
+        assert!(allowlist.allows(&mainnet, USDC));
+        assert!(allowlist.allows(&testnet, OTHER));
+        assert!(!allowlist.allows(&mainnet, OTHER));
+    }
+
+    #[test]
+    fn test_allowlist_with_no_match_rejects_all() {
+        let mainnet = x402_types::chain::ChainId::new("miden", "mainnet");
+        let allowlist = AssetAllowlist::new().with_network_assets(mainnet, [USDC.parse().unwrap()]);
+
+        // Only mainnet assets are allowed, so nothing on testnet is accepted.
+        assert!(allowlist.check(&requirement_for(USDC, 1)).is_err());
+        assert!(allowlist.check(&requirement_for(OTHER, 1)).is_err());
+    }
+
+    #[derive(Debug)]
+    struct MockClock(Mutex<SystemTime>);
+
+    impl MockClock {
+        fn at(unix_secs: u64) -> Arc<Self> {
+            Arc::new(Self(Mutex::new(
+                UNIX_EPOCH + Duration::from_secs(unix_secs),
+            )))
+        }
+
+        fn advance(&self, secs: u64) {
+            *self.0.lock().unwrap() += Duration::from_secs(secs);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> SystemTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    /// Reserves and commits a payment.
+    fn spend(budget: &dyn SpendingBudget, asset: &str, amount: u64) -> Result<(), SpendingError> {
+        let id = budget.reserve(asset, amount)?;
+        budget.commit(id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_budget_allows_spending_up_to_limit() {
+        let clock = MockClock::at(1_000);
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(3600))
+            .with_limit(USDC.parse().unwrap(), 50)
+            .with_clock(clock.clone());
+
+        assert!(spend(&budget, USDC, 30).is_ok());
+        assert!(spend(&budget, USDC, 20).is_ok());
+
+        assert_eq!(
+            budget.reserve(USDC, 1),
+            Err(SpendingError::BudgetExceeded {
+                requested: 1,
+                remaining: 0,
+                resets_at: 4_600
+            })
+        );
+    }
+
+    #[test]
+    fn test_budget_window_slides() {
+        let clock = MockClock::at(1_000);
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(3600))
+            .with_limit(USDC.parse().unwrap(), 50)
+            .with_clock(clock.clone());
+
+        spend(&budget, USDC, 30).unwrap();
+        clock.advance(1_800);
+        spend(&budget, USDC, 20).unwrap();
+
+        // 30 + 20 spent: nothing left until the first payment leaves the window.
+        let err = budget.reserve(USDC, 10).unwrap_err();
+        assert_eq!(
+            err,
+            SpendingError::BudgetExceeded {
+                requested: 10,
+                remaining: 0,
+                resets_at: 4_600
+            }
+        );
+
+        // Once the first payment has left the window, its 30 are available again.
+        clock.advance(1_800);
+        assert!(budget.reserve(USDC, 31).is_err());
+        assert!(budget.reserve(USDC, 30).is_ok());
+    }
+
+    #[test]
+    fn test_budget_is_per_faucet() {
+        let clock = MockClock::at(1_000);
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(60))
+            .with_limit(USDC.parse().unwrap(), 10)
+            .with_clock(clock);
+
+        spend(&budget, USDC, 10).unwrap();
+        assert!(budget.reserve(USDC, 1).is_err());
+
+        // Faucets without a limit are not restricted.
+        spend(&budget, OTHER, 1_000_000).unwrap();
+        assert!(budget.reserve(OTHER, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_budget_single_payment_over_limit() {
+        let clock = MockClock::at(1_000);
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(60))
+            .with_limit(USDC.parse().unwrap(), 10)
+            .with_clock(clock);
+
+        assert_eq!(
+            budget.reserve(USDC, 11),
+            Err(SpendingError::BudgetExceeded {
+                requested: 11,
+                remaining: 10,
+                resets_at: 1_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_reservations_count_until_released() {
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(60))
+            .with_limit(USDC.parse().unwrap(), 10)
+            .with_clock(MockClock::at(1_000));
+
+        let first = budget.reserve(USDC, 6).unwrap();
+        assert_eq!(
+            budget.reserve(USDC, 6),
+            Err(SpendingError::BudgetExceeded {
+                requested: 6,
+                remaining: 4,
+                resets_at: 1_000
+            })
+        );
+
+        budget.release(first);
+        let second = budget.reserve(USDC, 6).unwrap();
+        budget.commit(second);
+        assert!(budget.reserve(USDC, 5).is_err());
+    }
+
+    #[test]
+    fn test_shared_budget_cannot_be_overspent_concurrently() {
+        let budget: Arc<dyn SpendingBudget> = Arc::new(
+            InMemorySpendingBudget::new(Duration::from_secs(60))
+                .with_limit(USDC.parse().unwrap(), 10)
+                .with_clock(MockClock::at(1_000)),
+        );
+
+        let barrier = Arc::new(std::sync::Barrier::new(8));
+        let payers: Vec<_> = (0..8)
+            .map(|_| {
+                let budget = budget.clone();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    SpendingReservation::new(budget, USDC, 3)
+                })
+            })
+            .collect();
+        let reservations: Vec<_> = payers
+            .into_iter()
+            .filter_map(|payer| payer.join().unwrap().ok())
+            .collect();
+
+        assert_eq!(reservations.len(), 3);
+        reservations
+            .into_iter()
+            .for_each(SpendingReservation::commit);
+        assert!(budget.reserve(USDC, 2).is_err());
+    }
+
+    #[test]
+    fn test_dropped_reservation_is_released() {
+        let budget: Arc<dyn SpendingBudget> = Arc::new(
+            InMemorySpendingBudget::new(Duration::from_secs(60))
+                .with_limit(USDC.parse().unwrap(), 10)
+                .with_clock(MockClock::at(1_000)),
+        );
+
+        drop(SpendingReservation::new(budget.clone(), USDC, 10).unwrap());
+        SpendingReservation::new(budget.clone(), USDC, 10)
+            .unwrap()
+            .commit();
+        assert!(SpendingReservation::new(budget, USDC, 1).is_err());
+    }
+
+    #[test]
+    fn test_unparsable_asset_fails_closed_once_limited() {
+        let unlimited = InMemorySpendingBudget::new(Duration::from_secs(60));
+        assert!(spend(&unlimited, "not-a-faucet", 1).is_ok());
+
+        let limited = InMemorySpendingBudget::new(Duration::from_secs(60))
+            .with_limit(USDC.parse().unwrap(), 10);
+        assert_eq!(
+            limited.reserve("not-a-faucet", 1),
+            Err(SpendingError::UnrecognizedAsset {
+                asset: "not-a-faucet".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_budget_enforced_after_lock_poisoned() {
+        let budget = InMemorySpendingBudget::new(Duration::from_secs(60))
+            .with_limit(USDC.parse().unwrap(), 10)
+            .with_clock(MockClock::at(1_000));
+        spend(&budget, USDC, 10).unwrap();
+
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ledger = budget.ledger.lock().unwrap();
+            panic!("poison the ledger lock");
+        }));
+        assert!(budget.ledger.is_poisoned());
+
+        assert!(budget.reserve(USDC, 1).is_err());
+        spend(&budget, OTHER, 1).unwrap();
+        assert_eq!(
+            budget
+                .ledger
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .records
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_spending_error_into_x402_error() {
+        let err: x402_types::scheme::client::X402Error = SpendingError::AmountExceedsLimit {
+            requested: 2,
+            limit: 1,
+        }
+        .into();
+        assert!(err.to_string().contains("exceeds the per-payment limit"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Fixtures shared by the lightweight module's unit tests.
+
+use x402_types::chain::ChainId;
+
+use super::types::{
+    LIGHTWEIGHT_PAYLOAD_VERSION, LightweightPaymentHeader, LightweightPaymentRequirement,
+};
+
+/// The testnet USDC faucet.
+pub(crate) const FAUCET: &str = "0x37d5977a8e16d8205a360820f0230f";
+
+/// The account requirements pay to.
+pub(crate) const PAY_TO: &str = "0xaabbccddeeff00102233aabbccddee";
+
+/// A testnet requirement for 1 USDC.
+pub(crate) fn requirement() -> LightweightPaymentRequirement {
+    requirement_for(FAUCET, 1_000_000)
+}
+
+/// A testnet requirement for `amount` of `asset`.
+pub(crate) fn requirement_for(asset: &str, amount: u64) -> LightweightPaymentRequirement {
+    LightweightPaymentRequirement {
+        recipient_digest: "0xdigest".to_string(),
+        asset: asset.to_string(),
+        amount,
+        note_tag: 42,
+        network: ChainId::new("miden", "testnet"),
+        pay_to: PAY_TO.to_string(),
+        serial_num: None,
+    }
+}
+
+/// A payment header for `note_id`, with placeholder proof data.
+pub(crate) fn header(note_id: &str) -> LightweightPaymentHeader {
+    LightweightPaymentHeader {
+        payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
+        note_id: note_id.to_string(),
+        block_num: 10,
+        note_index: 0,
+        note_metadata: "0xaabb".to_string(),
+        inclusion_proof: "0xcafe".to_string(),
+    }
+}
+
+/// Payer that counts its calls and yields a few times to overlap with other
+/// tasks. Pays with note `0xnote-<account>`, or fails if `fail` is set.
+#[cfg(feature = "client")]
+#[derive(Default)]
+pub(crate) struct MockPayer {
+    pub(crate) account: String,
+    pub(crate) calls: std::sync::atomic::AtomicUsize,
+    pub(crate) fail: bool,
+}
+
+#[cfg(feature = "client")]
+impl MockPayer {
+    pub(crate) fn new(account: &str) -> std::sync::Arc<Self> {
+        std::sync::Arc::new(Self {
+            account: account.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "client")]
+#[async_trait::async_trait]
+impl super::client::LightweightPayerLike for MockPayer {
+    fn account_id(&self) -> String {
+        self.account.clone()
+    }
+
+    async fn create_and_submit_payment(
+        &self,
+        _requirement: &LightweightPaymentRequirement,
+    ) -> Result<LightweightPaymentHeader, x402_types::scheme::client::X402Error> {
+        self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+
+        if self.fail {
+            return Err(x402_types::scheme::client::X402Error::SigningError(
+                "Transaction submission failed".into(),
+            ));
+        }
+        Ok(header(&format!("0xnote-{}", self.account)))
+    }
+}
@@ -14,6 +14,16 @@
 use serde::{Deserialize, Serialize};
 use x402_types::chain::ChainId;
 
+use super::chain_state::DEFAULT_MAX_HEADER_FIELD_BYTES;
+#[cfg(feature = "miden-native")]
+use crate::chain::MidenAddressParseError;
+use crate::chain::MidenTransactionId;
+#[cfg(any(feature = "server", feature = "miden-native"))]
+use crate::chain::{MidenAccountAddress, MidenAccountType};
+use crate::v2_miden_exact::types::HexFieldError;
+#[cfg(any(feature = "server", feature = "miden-native"))]
+use crate::v2_miden_exact::types::MidenExactError;
+
 // ---------------------------------------------------------------------------
 // LightweightPaymentRequirement — what the server sends in the 402 response
 // ---------------------------------------------------------------------------
@@ -32,6 +42,9 @@ use x402_types::chain::ChainId;
 ///
 /// # Wire format (JSON, camelCase)
 ///
+/// Output is always camelCase; input also accepts the snake_case field
+/// names (`recipient_digest`, `note_tag`, ...).
+///
 /// ```json
 /// {
 ///   "recipientDigest": "0xabcdef...",
@@ -43,13 +56,14 @@ use x402_types::chain::ChainId;
 ///   "serialNum": "0x0102030405..."
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LightweightPaymentRequirement {
     /// The recipient digest (hex-encoded, 32 bytes).
     ///
     /// Computed as `hash(hash(serial_num, EMPTY), script_root, inputs_commitment)`
     /// where `inputs_commitment = hash(recipient_account_id)`.
+    #[serde(alias = "recipient_digest")]
     pub recipient_digest: String,
 
     /// The faucet (token) account ID (hex-encoded).
@@ -64,6 +78,7 @@ pub struct LightweightPaymentRequirement {
     ///
     /// The server picks a tag that allows it to efficiently filter for
     /// incoming notes via `sync_state`.
+    #[serde(alias = "note_tag")]
     pub note_tag: u32,
 
     /// The CAIP-2 chain identifier (e.g. `miden:testnet`).
@@ -74,6 +89,7 @@ pub struct LightweightPaymentRequirement {
     /// The agent needs this to construct the P2ID note (the `recipient_digest`
     /// alone is not sufficient to build the note — the agent also needs to
     /// know the target account). This is the raw account ID, not the digest.
+    #[serde(alias = "pay_to")]
     pub pay_to: String,
 
     /// Hex-encoded serial number (32 bytes).
@@ -86,10 +102,29 @@ pub struct LightweightPaymentRequirement {
     /// Kept as `Option<String>` at the type level for backwards compatibility
     /// (bobbinth's design allows omission for privacy), but in practice
     /// `create_payment_requirement()` always populates this field.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, alias = "serial_num", skip_serializing_if = "Option::is_none")]
     pub serial_num: Option<String>,
 }
 
+/// Redacts `serial_num`, which together with the note lets anyone compute
+/// the payment note's nullifier.
+impl std::fmt::Debug for LightweightPaymentRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LightweightPaymentRequirement")
+            .field("recipient_digest", &self.recipient_digest)
+            .field("asset", &self.asset)
+            .field("amount", &self.amount)
+            .field("note_tag", &self.note_tag)
+            .field("network", &self.network)
+            .field("pay_to", &self.pay_to)
+            .field(
+                "serial_num",
+                &self.serial_num.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // LightweightPaymentHeader — what the agent sends back
 // ---------------------------------------------------------------------------
@@ -102,8 +137,12 @@ pub struct LightweightPaymentRequirement {
 ///
 /// # Wire format (JSON, camelCase)
 ///
+/// Output is always camelCase; input also accepts the snake_case field
+/// names (`note_id`, `block_num`, ...).
+///
 /// ```json
 /// {
+///   "payloadVersion": 1,
 ///   "noteId": "0xdeadbeef...",
 ///   "blockNum": 42,
 ///   "noteIndex": 5,
@@ -111,19 +150,31 @@ pub struct LightweightPaymentRequirement {
 ///   "inclusionProof": "0xcafebabe..."
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LightweightPaymentHeader {
+    /// The payload format version, [`LIGHTWEIGHT_PAYLOAD_VERSION`] for
+    /// headers built by this crate.
+    ///
+    /// Headers from clients predating the field default to version 1.
+    /// Facilitators reject versions they do not know with
+    /// [`PayloadValidationError::UnsupportedVersion`], so an outdated client
+    /// can be told apart from a malformed payload.
+    #[serde(default = "default_payload_version", alias = "payload_version")]
+    pub payload_version: u8,
+
     /// The note ID (hex-encoded, 32 bytes).
     ///
     /// The server verifies this matches the expected note ID computed from
     /// `hash(recipient_digest, asset_commitment)`.
+    #[serde(alias = "note_id")]
     pub note_id: String,
 
     /// The block number in which the note was included.
     ///
     /// The server fetches the block header for this block number to verify
     /// the inclusion proof against the block's note commitment root.
+    #[serde(alias = "block_num")]
     pub block_num: u32,
 
     /// The note's index in the block's note tree (SparseMerkleTree).
@@ -135,6 +186,7 @@ pub struct LightweightPaymentHeader {
     ///
     /// Miden's note tree supports up to 2^16 notes per block (`SimpleSmt<16>`),
     /// so `u16` is sufficient.
+    #[serde(alias = "note_index")]
     pub note_index: u16,
 
     /// The note metadata (hex-encoded serialized `NoteMetadata`).
@@ -144,15 +196,189 @@ pub struct LightweightPaymentHeader {
     /// compute the note commitment (`hash(note_id || metadata_commitment)`)
     /// which is the leaf value in the block's note tree. Required for
     /// Merkle path verification.
+    #[serde(alias = "note_metadata")]
     pub note_metadata: String,
 
     /// The Merkle inclusion proof (hex-encoded `SparseMerklePath`).
     ///
     /// Proves that the note is included in the note tree of the specified
     /// block. Verification is a sequence of O(log n) hash operations.
+    #[serde(alias = "inclusion_proof")]
     pub inclusion_proof: String,
 }
 
+/// Abbreviates `note_metadata` and `inclusion_proof` to their length and
+/// first and last bytes, so logging a header does not dump the full proof.
+impl std::fmt::Debug for LightweightPaymentHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LightweightPaymentHeader")
+            .field("payload_version", &self.payload_version)
+            .field("note_id", &self.note_id)
+            .field("block_num", &self.block_num)
+            .field("note_index", &self.note_index)
+            .field("note_metadata", &HexPreview(&self.note_metadata))
+            .field("inclusion_proof", &HexPreview(&self.inclusion_proof))
+            .finish()
+    }
+}
+
+/// Debug-formats a hex field as its length plus its first and last
+/// [`HexPreview::EDGE_BYTES`] bytes, e.g. `"0xaabbcc..ddeeff" (96 bytes)`.
+struct HexPreview<'a>(&'a str);
+
+impl HexPreview<'_> {
+    /// Number of bytes shown at each end.
+    const EDGE_BYTES: usize = 3;
+}
+
+impl std::fmt::Debug for HexPreview<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex_str = self.0.strip_prefix("0x").unwrap_or(self.0);
+        let edge = 2 * Self::EDGE_BYTES;
+        let bytes = hex_str.len().div_ceil(2);
+        if hex_str.len() <= 2 * edge {
+            return write!(f, "{:?} ({bytes} bytes)", self.0);
+        }
+        match (hex_str.get(..edge), hex_str.get(hex_str.len() - edge..)) {
+            (Some(head), Some(tail)) => write!(f, "\"0x{head}..{tail}\" ({bytes} bytes)"),
+            // Not hex, so not worth previewing.
+            _ => write!(f, "<{bytes} bytes>"),
+        }
+    }
+}
+
+/// The current (and highest understood) [`LightweightPaymentHeader`]
+/// payload format version.
+pub const LIGHTWEIGHT_PAYLOAD_VERSION: u8 = 1;
+
+/// The version of headers without a `payloadVersion` field.
+///
+/// Clients predating the field sent the version 1 format, so a missing
+/// version means v1 even after [`LIGHTWEIGHT_PAYLOAD_VERSION`] moves on.
+const LEGACY_PAYLOAD_VERSION: u8 = 1;
+
+fn default_payload_version() -> u8 {
+    LEGACY_PAYLOAD_VERSION
+}
+
+/// Length of a note ID in bytes (one `Word`).
+pub const NOTE_ID_BYTE_LEN: usize = 32;
+
+/// Size limits checked by [`LightweightPaymentHeader::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadLimits {
+    /// Maximum decoded size, in bytes, of `note_metadata` and
+    /// `inclusion_proof`.
+    pub max_field_bytes: usize,
+}
+
+impl Default for PayloadLimits {
+    fn default() -> Self {
+        Self {
+            max_field_bytes: DEFAULT_MAX_HEADER_FIELD_BYTES,
+        }
+    }
+}
+
+impl LightweightPaymentHeader {
+    /// Checks the header's structure without any cryptography or RPC.
+    ///
+    /// The payload version must be one this crate understands (1 through
+    /// [`LIGHTWEIGHT_PAYLOAD_VERSION`]), and the hex fields must decode:
+    /// `note_id` to exactly [`NOTE_ID_BYTE_LEN`] bytes, `note_metadata` and
+    /// `inclusion_proof` to at most `limits.max_field_bytes`. A header passing these checks can
+    /// still fail verification; one failing them never passes, so the HTTP
+    /// layer can reject it as a bad request before queuing verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`PayloadValidationError`] found.
+    pub fn validate(&self, limits: &PayloadLimits) -> Result<(), PayloadValidationError> {
+        if !(1..=LIGHTWEIGHT_PAYLOAD_VERSION).contains(&self.payload_version) {
+            return Err(PayloadValidationError::UnsupportedVersion {
+                got: self.payload_version,
+                supported: LIGHTWEIGHT_PAYLOAD_VERSION,
+            });
+        }
+        let note_id_len = check_hex_field("note_id", &self.note_id, usize::MAX)?;
+        if note_id_len != NOTE_ID_BYTE_LEN {
+            return Err(PayloadValidationError::Malformed {
+                field: "note_id".to_string(),
+                reason: HexFieldError::WrongLength {
+                    expected: NOTE_ID_BYTE_LEN,
+                    got: note_id_len,
+                },
+            });
+        }
+        check_hex_field("note_metadata", &self.note_metadata, limits.max_field_bytes)?;
+        check_hex_field(
+            "inclusion_proof",
+            &self.inclusion_proof,
+            limits.max_field_bytes,
+        )?;
+        Ok(())
+    }
+}
+
+/// Why a payment header failed [`LightweightPaymentHeader::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PayloadValidationError {
+    /// The header uses a payload format version this crate does not know.
+    #[error("Unsupported payload version {got}, supported version is {supported}")]
+    UnsupportedVersion { got: u8, supported: u8 },
+    /// A hex field decodes to more bytes than allowed.
+    #[error("{field} is {got} bytes, exceeding the maximum of {max}")]
+    TooLarge {
+        field: String,
+        got: usize,
+        max: usize,
+    },
+    /// A hex field is not valid hex, or has the wrong length.
+    #[error("Malformed {field}: {reason}")]
+    Malformed {
+        field: String,
+        reason: HexFieldError,
+    },
+}
+
+impl PayloadValidationError {
+    /// Returns the offending field.
+    pub fn field(&self) -> &str {
+        match self {
+            PayloadValidationError::UnsupportedVersion { .. } => "payload_version",
+            PayloadValidationError::TooLarge { field, .. }
+            | PayloadValidationError::Malformed { field, .. } => field,
+        }
+    }
+
+    /// Returns a machine-readable reason code: `unsupported_version`, or one
+    /// of the codes of
+    /// [`MidenExactError::field_error`](crate::v2_miden_exact::MidenExactError::field_error).
+    pub fn code(&self) -> &'static str {
+        match self {
+            PayloadValidationError::UnsupportedVersion { .. } => "unsupported_version",
+            PayloadValidationError::TooLarge { .. } => "too_large",
+            PayloadValidationError::Malformed { reason, .. } => reason.code(),
+        }
+    }
+}
+
+impl From<PayloadValidationError> for crate::v2_miden_exact::types::MidenExactError {
+    fn from(value: PayloadValidationError) -> Self {
+        match value {
+            PayloadValidationError::UnsupportedVersion { got, supported } => {
+                Self::UnsupportedPayloadVersion { got, supported }
+            }
+            PayloadValidationError::TooLarge { field, got, max } => {
+                Self::PayloadTooLarge { field, got, max }
+            }
+            PayloadValidationError::Malformed { field, reason } => {
+                Self::MalformedField { field, reason }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PaymentContext — server-side state for a pending payment
 // ---------------------------------------------------------------------------
@@ -243,6 +469,12 @@ impl PaymentContext {
             .as_secs();
         now.saturating_sub(self.created_at) >= timeout_secs
     }
+
+    /// Returns the Unix timestamp (seconds) at which this context expires
+    /// under the given timeout.
+    pub fn expires_at(&self, timeout_secs: u64) -> u64 {
+        self.created_at.saturating_add(timeout_secs)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -268,12 +500,239 @@ pub struct LightweightVerifyResponse {
     /// An error message if verification failed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+
+    /// Details of the note that satisfied the payment.
+    ///
+    /// Populated on successful verification so resource servers can log
+    /// the note and later reconcile it against on-chain data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<VerifiedNoteDetails>,
+}
+
+/// Details of a verified payment note.
+///
+/// Everything here was either bound by the `NoteId` check (asset and
+/// amount) or by the note commitment in the Merkle inclusion proof
+/// (metadata fields such as the sender, note type and tag).
+///
+/// # Wire format (JSON, camelCase)
+///
+/// ```json
+/// {
+///   "noteCommitment": "0x5e6f...",
+///   "noteIndex": 5,
+///   "asset": "0x37d5977a8e16d8205a360820f0230f",
+///   "amount": 1000000,
+///   "sender": "0x0b50cc0489f8f1101e946691aa89ca",
+///   "noteType": "private",
+///   "noteTag": 12345
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedNoteDetails {
+    /// The note commitment (`hash(note_id || metadata_commitment)`) found
+    /// in the block's note tree (hex-encoded).
+    ///
+    /// Records exactly which leaf the inclusion proof was checked against,
+    /// so a payment can be matched against the chain later without
+    /// revealing the note's contents.
+    pub note_commitment: String,
+
+    /// The note's index in the block's note tree.
+    pub note_index: u16,
+
+    /// The faucet (token) account ID of the paid asset (hex-encoded).
+    pub asset: String,
+
+    /// The paid amount in the token's smallest unit.
+    pub amount: u64,
+
+    /// The account that created the note, taken from the note metadata
+    /// (hex-encoded).
+    pub sender: String,
+
+    /// The note type from the note metadata (`"public"` or `"private"`).
+    pub note_type: String,
+
+    /// The note tag from the note metadata.
+    ///
+    /// Equal to the tag issued in the payment requirement unless tag
+    /// enforcement is disabled, so servers that encode an order reference
+    /// in the tag can match the payment to it.
+    pub note_tag: u32,
+}
+
+// ---------------------------------------------------------------------------
+// PaymentReceipt — agent-side record of a payment
+// ---------------------------------------------------------------------------
+
+/// Agent-side record of a lightweight payment.
+///
+/// The payment header only carries what the server needs to verify the
+/// note. The receipt adds what the agent needs to account for its spending
+/// and later prove that it paid, and can be persisted as JSON.
+///
+/// # Wire format (JSON, camelCase)
+///
+/// ```json
+/// {
+///   "transactionId": "0x1f2e...",
+///   "noteId": "0xabcdef...",
+///   "blockNum": 42,
+///   "payer": "0x0b50cc0489f8f1101e946691aa89ca",
+///   "payTo": "0xaabbccddeeff...",
+///   "faucet": "0x37d5977a8e16d8205a360820f0230f",
+///   "amount": 1000000,
+///   "network": "miden:testnet",
+///   "createdAt": 1760000000
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentReceipt {
+    /// The ID of the transaction that created the note (hex-encoded), if
+    /// known to the payer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<MidenTransactionId>,
+
+    /// The ID of the payment note (hex-encoded).
+    pub note_id: String,
+
+    /// The block number in which the note was included.
+    pub block_num: u32,
+
+    /// The paying account ID (hex-encoded).
+    pub payer: String,
+
+    /// The recipient's account ID (hex-encoded).
+    pub pay_to: String,
+
+    /// The faucet (token) account ID of the paid asset (hex-encoded).
+    pub faucet: String,
+
+    /// The paid amount in the token's smallest unit.
+    pub amount: u64,
+
+    /// The CAIP-2 chain identifier of the network the payment was made on.
+    pub network: ChainId,
+
+    /// When the receipt was created, as a Unix timestamp (seconds since epoch).
+    pub created_at: u64,
+}
+
+impl PaymentReceipt {
+    /// Creates a receipt for a payment `payer` made for `requirement`.
+    ///
+    /// `created_at` is set to the current time as seconds since the Unix epoch.
+    pub fn new(
+        payer: String,
+        requirement: &LightweightPaymentRequirement,
+        header: &LightweightPaymentHeader,
+        transaction_id: Option<MidenTransactionId>,
+    ) -> Self {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is before Unix epoch")
+            .as_secs();
+        Self {
+            transaction_id,
+            note_id: header.note_id.clone(),
+            block_num: header.block_num,
+            payer,
+            pay_to: requirement.pay_to.clone(),
+            faucet: requirement.asset.clone(),
+            amount: requirement.amount,
+            network: requirement.network.clone(),
+            created_at,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
 
+/// Describes a JSON deserialization error for an HTTP error response.
+///
+/// A missing field is reported with both accepted spellings, e.g.
+/// ``missing field `noteId` (accepted spellings: `noteId`, `note_id`)``,
+/// since camelCase-only messages confuse clients sending snake_case.
+pub fn describe_payload_error(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(field, _)| field)
+    else {
+        return message;
+    };
+    let snake = camel_to_snake_case(field);
+    if snake == field {
+        return message;
+    }
+    format!("{message} (accepted spellings: `{field}`, `{snake}`)")
+}
+
+fn camel_to_snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    for c in s.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Checks a hex-encoded payment header field, with an optional `0x` prefix,
+/// and returns its decoded length.
+///
+/// The size limit is checked before the characters, so oversized inputs are
+/// rejected without scanning them.
+pub(crate) fn check_hex_field(
+    field: &str,
+    value: &str,
+    max_bytes: usize,
+) -> Result<usize, PayloadValidationError> {
+    let malformed = |reason| PayloadValidationError::Malformed {
+        field: field.to_string(),
+        reason,
+    };
+    let hex_str = value.strip_prefix("0x").unwrap_or(value);
+
+    if hex_str.is_empty() {
+        return Err(malformed(HexFieldError::Empty));
+    }
+
+    let decoded_len = hex_str.len().div_ceil(2);
+    if decoded_len > max_bytes {
+        return Err(PayloadValidationError::TooLarge {
+            field: field.to_string(),
+            got: decoded_len,
+            max: max_bytes,
+        });
+    }
+
+    if let Some((position, character)) =
+        hex_str.char_indices().find(|(_, c)| !c.is_ascii_hexdigit())
+    {
+        return Err(malformed(HexFieldError::NotHex {
+            position,
+            character,
+        }));
+    }
+
+    if !hex_str.len().is_multiple_of(2) {
+        return Err(malformed(HexFieldError::OddLength(hex_str.len())));
+    }
+
+    Ok(decoded_len)
+}
+
 /// Parses a hex-encoded serial number (32 bytes) into a Miden `Word` (`[Felt; 4]`).
 ///
 /// Each of the four `Felt` values is constructed from 8 little-endian bytes.
@@ -302,6 +761,50 @@ pub fn parse_serial_num_hex(serial_num_hex: &str) -> Result<miden_protocol::Word
     ]))
 }
 
+/// Checks that an asset account ID is a fungible faucet.
+///
+/// Only reads the type bits of the ID, so it needs no network access.
+///
+/// # Errors
+///
+/// Returns [`MidenExactError::InvalidFaucet`] if the account is not a fungible
+/// faucet.
+#[cfg(any(feature = "server", feature = "miden-native"))]
+pub(crate) fn check_fungible_faucet(faucet: &MidenAccountAddress) -> Result<(), MidenExactError> {
+    let account_type = faucet.account_type();
+    if account_type != MidenAccountType::FungibleFaucet {
+        return Err(MidenExactError::InvalidFaucet(format!(
+            "asset '{faucet}' is a {account_type:?} account, expected a fungible faucet"
+        )));
+    }
+    Ok(())
+}
+
+/// Parses a hex-encoded asset account ID and checks that it is a fungible faucet.
+///
+/// Catches a regular account ID passed as the asset up front, instead of
+/// surfacing it later as a confusing `FungibleAsset` construction or
+/// NoteId mismatch error.
+///
+/// # Errors
+///
+/// Returns [`MidenExactError::DeserializationError`] if the hex is not a valid
+/// account ID, and [`MidenExactError::InvalidFaucet`] if the account is not a
+/// fungible faucet.
+#[cfg(feature = "miden-native")]
+pub(crate) fn parse_fungible_faucet_id(
+    faucet_id_hex: &str,
+) -> Result<miden_protocol::account::AccountId, MidenExactError> {
+    let invalid = |e: MidenAddressParseError| {
+        MidenExactError::DeserializationError(format!(
+            "Invalid faucet account ID '{faucet_id_hex}': {e}"
+        ))
+    };
+    let faucet: MidenAccountAddress = faucet_id_hex.parse().map_err(invalid)?;
+    check_fungible_faucet(&faucet)?;
+    miden_protocol::account::AccountId::try_from(&faucet).map_err(invalid)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -309,6 +812,7 @@ pub fn parse_serial_num_hex(serial_num_hex: &str) -> Result<miden_protocol::Word
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lightweight::test_fixtures::{header, requirement};
 
     #[test]
     fn test_payment_requirement_serde_roundtrip() {
@@ -364,6 +868,7 @@ mod tests {
     #[test]
     fn test_payment_header_serde_roundtrip() {
         let header = LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
                 .to_string(),
             block_num: 42,
@@ -393,9 +898,11 @@ mod tests {
             note_id: "0xabcd".to_string(),
             block_num: 100,
             error: None,
+            details: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(!json.contains("\"error\""));
+        assert!(!json.contains("\"details\""));
 
         let deserialized: LightweightVerifyResponse = serde_json::from_str(&json).unwrap();
         assert!(deserialized.valid);
@@ -411,6 +918,7 @@ mod tests {
             note_id: "0xabcd".to_string(),
             block_num: 100,
             error: Some("NoteId mismatch".to_string()),
+            details: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"error\""));
@@ -420,6 +928,83 @@ mod tests {
         assert_eq!(deserialized.error.as_deref(), Some("NoteId mismatch"));
     }
 
+    #[test]
+    fn test_verify_response_details_json_shape() {
+        let resp = LightweightVerifyResponse {
+            valid: true,
+            note_id: "0xabcd".to_string(),
+            block_num: 100,
+            error: None,
+            details: Some(VerifiedNoteDetails {
+                note_commitment: "0x5e6f".to_string(),
+                note_index: 5,
+                asset: "0x37d5977a8e16d8205a360820f0230f".to_string(),
+                amount: 1_000_000,
+                sender: "0x0b50cc0489f8f1101e946691aa89ca".to_string(),
+                note_type: "private".to_string(),
+                note_tag: 12345,
+            }),
+        };
+        let json = serde_json::to_value(&resp).unwrap();
+        assert_eq!(
+            json["details"],
+            serde_json::json!({
+                "noteCommitment": "0x5e6f",
+                "noteIndex": 5,
+                "asset": "0x37d5977a8e16d8205a360820f0230f",
+                "amount": 1000000,
+                "sender": "0x0b50cc0489f8f1101e946691aa89ca",
+                "noteType": "private",
+                "noteTag": 12345,
+            })
+        );
+
+        let deserialized: LightweightVerifyResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.details, resp.details);
+    }
+
+    #[test]
+    fn test_verify_response_without_details_deserializes() {
+        let json = r#"{"valid": true, "noteId": "0xabcd", "blockNum": 100}"#;
+        let resp: LightweightVerifyResponse = serde_json::from_str(json).unwrap();
+        assert!(resp.details.is_none());
+    }
+
+    #[test]
+    fn test_payment_receipt_serde_roundtrip() {
+        let req = requirement();
+        let header = LightweightPaymentHeader {
+            block_num: 42,
+            note_index: 3,
+            ..header("0xnote")
+        };
+        let receipt = PaymentReceipt::new(
+            "0x0b50cc0489f8f1101e946691aa89ca".to_string(),
+            &req,
+            &header,
+            Some(format!("0x{}", "AB".repeat(32)).parse().unwrap()),
+        );
+        assert_eq!(receipt.note_id, "0xnote");
+        assert_eq!(receipt.block_num, 42);
+        assert_eq!(receipt.faucet, req.asset);
+        assert_eq!(receipt.pay_to, req.pay_to);
+        assert_eq!(receipt.amount, 1_000_000);
+        assert!(receipt.created_at > 0);
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        // The transaction ID is normalized to lowercase with a 0x prefix.
+        assert_eq!(json["transactionId"], format!("0x{}", "ab".repeat(32)));
+        assert_eq!(json["payTo"], "0xaabbccddeeff00102233aabbccddee");
+        assert_eq!(json["network"], "miden:testnet");
+        let deserialized: PaymentReceipt = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, receipt);
+
+        // Receipts without a transaction ID omit the field.
+        let without_tx = PaymentReceipt::new("0x01".to_string(), &req, &header, None);
+        let json = serde_json::to_string(&without_tx).unwrap();
+        assert!(!json.contains("transactionId"));
+    }
+
     #[test]
     fn test_payment_context_new() {
         let ctx = PaymentContext::new(
@@ -452,6 +1037,23 @@ mod tests {
         assert!(ctx.is_expired(0));
     }
 
+    #[test]
+    fn test_payment_context_expires_at() {
+        let mut ctx = PaymentContext::new(
+            "0xaabb".to_string(),
+            "0xccdd".to_string(),
+            1_000_000,
+            42,
+            None,
+        );
+        ctx.created_at = 1_700_000_000;
+        assert_eq!(ctx.expires_at(300), 1_700_000_300);
+        assert!(ctx.is_expired(300));
+
+        ctx.created_at = u64::MAX - 10;
+        assert_eq!(ctx.expires_at(300), u64::MAX);
+    }
+
     #[test]
     fn test_payment_requirement_deserialize_missing_serial_num() {
         let json = r#"{
@@ -460,7 +1062,7 @@ mod tests {
             "amount": 100,
             "noteTag": 1,
             "network": "miden:testnet",
-            "payTo": "0xaabbccddeeff00112233aabbccddee"
+            "payTo": "0xaabbccddeeff00102233aabbccddee"
         }"#;
         let req: LightweightPaymentRequirement = serde_json::from_str(json).unwrap();
         assert!(req.serial_num.is_none());
@@ -469,6 +1071,7 @@ mod tests {
     #[test]
     fn test_payment_header_json_camel_case_keys() {
         let header = LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xaa".to_string(),
             block_num: 1,
             note_index: 0,
@@ -488,4 +1091,196 @@ mod tests {
         assert!(!json.contains("\"note_metadata\""));
         assert!(!json.contains("\"inclusion_proof\""));
     }
+
+    fn valid_header() -> LightweightPaymentHeader {
+        LightweightPaymentHeader {
+            payload_version: LIGHTWEIGHT_PAYLOAD_VERSION,
+            note_id: format!("0x{}", "ab".repeat(NOTE_ID_BYTE_LEN)),
+            block_num: 42,
+            note_index: 3,
+            note_metadata: "0xaabb".to_string(),
+            inclusion_proof: "cafe".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_header_validate_accepts_well_formed() {
+        assert_eq!(valid_header().validate(&PayloadLimits::default()), Ok(()));
+        let limits = PayloadLimits { max_field_bytes: 2 };
+        assert_eq!(valid_header().validate(&limits), Ok(()));
+    }
+
+    #[test]
+    fn test_header_validate_rejects_each_violation() {
+        let limits = PayloadLimits { max_field_bytes: 4 };
+        let cases = [
+            ("note_id", "0x".to_string(), "empty"),
+            ("note_id", "0xnote1".to_string(), "not_hex"),
+            ("note_id", "abc".to_string(), "odd_length"),
+            ("note_id", "ab".repeat(31), "wrong_length"),
+            ("note_id", "ab".repeat(33), "wrong_length"),
+            ("note_metadata", String::new(), "empty"),
+            ("note_metadata", "0xaabbccddee".to_string(), "too_large"),
+            ("inclusion_proof", "0xca fe".to_string(), "not_hex"),
+            ("inclusion_proof", "0xcaf".to_string(), "odd_length"),
+            ("inclusion_proof", "ab".repeat(5), "too_large"),
+        ];
+
+        for (field, value, code) in cases {
+            let mut header = valid_header();
+            match field {
+                "note_id" => header.note_id = value,
+                "note_metadata" => header.note_metadata = value,
+                _ => header.inclusion_proof = value,
+            }
+            let err = header.validate(&limits).unwrap_err();
+            assert_eq!((err.field(), err.code()), (field, code), "{err}");
+
+            // The same classification survives conversion to MidenExactError.
+            let exact: crate::v2_miden_exact::types::MidenExactError = err.into();
+            assert_eq!(exact.field_error(), Some((field, code)));
+            assert!(exact.is_verification_error());
+        }
+    }
+
+    #[test]
+    fn test_debug_redacts_large_and_secret_fields() {
+        let proof = format!("0x{}", "cd".repeat(200));
+        let header = LightweightPaymentHeader {
+            note_metadata: format!("0x0102{}0304", "ee".repeat(60)),
+            inclusion_proof: proof.clone(),
+            ..valid_header()
+        };
+        let debug = format!("{header:?}");
+        assert!(!debug.contains(&proof), "{debug}");
+        assert!(!debug.contains(&"ee".repeat(60)), "{debug}");
+        assert!(debug.contains("\"0x0102ee..ee0304\" (64 bytes)"), "{debug}");
+        assert!(debug.contains("(200 bytes)"), "{debug}");
+        assert!(debug.contains(&header.note_id), "{debug}");
+
+        let serial_num = format!("0x{}", "5a".repeat(32));
+        let requirement = LightweightPaymentRequirement {
+            serial_num: Some(serial_num.clone()),
+            ..requirement()
+        };
+        let debug = format!("{requirement:?}");
+        assert!(!debug.contains(&serial_num), "{debug}");
+        assert!(debug.contains("<redacted>"), "{debug}");
+    }
+
+    #[test]
+    fn test_payload_version_migration() {
+        let mut json = serde_json::to_value(valid_header()).unwrap();
+        assert_eq!(json["payloadVersion"], LIGHTWEIGHT_PAYLOAD_VERSION);
+
+        // Version-absent payloads from older clients read as version 1.
+        json.as_object_mut().unwrap().remove("payloadVersion");
+        let header: LightweightPaymentHeader = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(header.payload_version, 1);
+        assert_eq!(header.validate(&PayloadLimits::default()), Ok(()));
+
+        json["payloadVersion"] = 1.into();
+        let header: LightweightPaymentHeader = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(header.validate(&PayloadLimits::default()), Ok(()));
+
+        // A future version deserializes but is rejected with both versions named.
+        json["payloadVersion"] = 2.into();
+        let header: LightweightPaymentHeader = serde_json::from_value(json).unwrap();
+        let err = header.validate(&PayloadLimits::default()).unwrap_err();
+        assert_eq!(
+            err,
+            PayloadValidationError::UnsupportedVersion {
+                got: 2,
+                supported: LIGHTWEIGHT_PAYLOAD_VERSION,
+            }
+        );
+        assert_eq!(
+            (err.field(), err.code()),
+            ("payload_version", "unsupported_version")
+        );
+        let exact: crate::v2_miden_exact::types::MidenExactError = err.into();
+        assert!(exact.is_verification_error());
+        assert!(exact.to_string().contains("version 2"), "{exact}");
+
+        let mut header = valid_header();
+        header.payload_version = 0;
+        assert!(matches!(
+            header.validate(&PayloadLimits::default()),
+            Err(PayloadValidationError::UnsupportedVersion { got: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_snake_case_aliases_accepted() {
+        let camel = serde_json::json!({
+            "payloadVersion": 1,
+            "noteId": "0xnote",
+            "blockNum": 42,
+            "noteIndex": 3,
+            "noteMetadata": "0xaabb",
+            "inclusionProof": "0xcafe",
+        });
+        let snake = serde_json::json!({
+            "note_id": "0xnote",
+            "block_num": 42,
+            "note_index": 3,
+            "note_metadata": "0xaabb",
+            "inclusion_proof": "0xcafe",
+        });
+        let mixed = serde_json::json!({
+            "noteId": "0xnote",
+            "block_num": 42,
+            "noteIndex": 3,
+            "note_metadata": "0xaabb",
+            "inclusionProof": "0xcafe",
+        });
+        for json in [camel.clone(), snake, mixed] {
+            let header: LightweightPaymentHeader = serde_json::from_value(json).unwrap();
+            assert_eq!(header.block_num, 42);
+            assert_eq!(header.note_metadata, "0xaabb");
+            // Output stays camelCase.
+            assert_eq!(serde_json::to_value(&header).unwrap(), camel);
+        }
+
+        let requirement: LightweightPaymentRequirement =
+            serde_json::from_value(serde_json::json!({
+                "recipient_digest": "0xdigest",
+                "asset": "0x37d5977a8e16d8205a360820f0230f",
+                "amount": 1000,
+                "note_tag": 7,
+                "network": "miden:testnet",
+                "payTo": "0xaabbccddeeff00102233aabbccddee",
+                "serial_num": "0x01",
+            }))
+            .unwrap();
+        assert_eq!(requirement.recipient_digest, "0xdigest");
+        assert_eq!(requirement.note_tag, 7);
+        assert_eq!(requirement.serial_num.as_deref(), Some("0x01"));
+        let json = serde_json::to_value(&requirement).unwrap();
+        assert_eq!(json["recipientDigest"], "0xdigest");
+        assert_eq!(json["serialNum"], "0x01");
+    }
+
+    #[test]
+    fn test_describe_payload_error_names_spellings() {
+        let err = serde_json::from_str::<LightweightPaymentHeader>(
+            r#"{"blockNum": 1, "noteIndex": 0, "noteMetadata": "0x", "inclusionProof": "0x"}"#,
+        )
+        .unwrap_err();
+        let message = describe_payload_error(&err);
+        assert!(message.starts_with("missing field `noteId`"), "{message}");
+        assert!(
+            message.ends_with("(accepted spellings: `noteId`, `note_id`)"),
+            "{message}"
+        );
+
+        // Single-word fields and other errors are passed through.
+        let err = serde_json::from_str::<LightweightPaymentRequirement>(
+            r#"{"recipientDigest": "0x", "amount": 1, "noteTag": 0, "network": "miden:testnet", "payTo": "0x"}"#,
+        )
+        .unwrap_err();
+        assert_eq!(describe_payload_error(&err), err.to_string());
+        let err = serde_json::from_str::<LightweightPaymentHeader>("{").unwrap_err();
+        assert_eq!(describe_payload_error(&err), err.to_string());
+    }
 }
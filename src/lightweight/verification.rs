@@ -25,17 +25,22 @@
 //!           │                                │
 //!           ▼                                ▼
 //!  ┌─────────────────────────────────────────────────┐
+//!  │ 0. Check the header's structure (validate)       │
 //!  │ 1. Check expiry                                  │
 //!  │ 2. expected_note_id = hash(recipient, asset)     │
-//!  │ 3. assert note_id == expected_note_id            │
+//!  │ 3. assert note_id == expected_note_id, tag match │
 //!  │ 4. Fetch block header (cache or RPC)             │
 //!  │ 5. SparseMerklePath.verify(note_root)            │
-//!  │ 6. Return LightweightVerifyResponse              │
+//!  │ 6. Return LightweightVerifyResponse + details    │
 //!  └─────────────────────────────────────────────────┘
 //! ```
 
 use super::chain_state::FacilitatorChainState;
 use super::types::{LightweightPaymentHeader, LightweightVerifyResponse, PaymentContext};
+#[cfg(feature = "miden-native")]
+use super::types::{PayloadLimits, VerifiedNoteDetails};
+#[cfg(test)]
+use crate::v2_miden_exact::types::HexFieldError;
 use crate::v2_miden_exact::types::MidenExactError;
 
 /// Default timeout (in seconds) for payment contexts when none is specified.
@@ -46,17 +51,21 @@ const DEFAULT_PAYMENT_TIMEOUT_SECS: u64 = 300;
 ///
 /// This implements bobbinth's design from 0xMiden/node#1796:
 ///
-/// 1. Check that the payment context has not expired.
+/// 1. Check that the header is well-formed (see
+///    [`LightweightPaymentHeader::validate`]) and that the payment context
+///    has not expired.
 /// 2. Reconstruct `expected_note_id = hash(recipient_digest, asset_commitment)`:
 ///    - Parse `recipient_digest` from hex into a `Word`
 ///    - Compute the asset commitment from `FungibleAsset::new(faucet_id, amount)`
 ///    - Compute the `NoteId` using miden-protocol's hashing
-/// 3. Compare the agent's `note_id` with the expected value.
+/// 3. Compare the agent's `note_id` with the expected value, and check that
+///    the note's tag is the one issued in the payment requirement.
 /// 4. Get the block header for `block_num` from the chain state cache (falls
 ///    back to RPC if not cached).
 /// 5. Verify the `SparseMerklePath` (the `inclusion_proof`) against the block's
 ///    `note_root`.
-/// 6. Return a [`LightweightVerifyResponse`].
+/// 6. Return a [`LightweightVerifyResponse`] whose `details` describe the
+///    matched note (index, faucet, amount, sender and note type).
 ///
 /// # Arguments
 ///
@@ -70,26 +79,99 @@ const DEFAULT_PAYMENT_TIMEOUT_SECS: u64 = 300;
 /// The `miden-native` feature is required for the cryptographic operations
 /// (NoteId reconstruction, SparseMerklePath verification). Without it, this
 /// function returns an error.
+///
+/// # Tracing
+///
+/// With the `tracing` feature, the call runs in a `verify_lightweight_payment`
+/// span recording the network, faucet (shortened), amount, note id, block
+/// number, header payload size and the outcome. The serial number and
+/// recipient digest are never recorded.
 #[cfg(feature = "miden-native")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "verify_lightweight_payment",
+        skip_all,
+        fields(
+            network = %chain_state.chain_reference(),
+            faucet = %short_account_id(&payment_context.asset_faucet_id),
+            amount = payment_context.amount,
+            note_id = %payment_header.note_id,
+            block_num = payment_header.block_num,
+            note_index = payment_header.note_index,
+            payload_bytes = payment_header.note_metadata.len() + payment_header.inclusion_proof.len(),
+            outcome = tracing::field::Empty,
+            error = tracing::field::Empty,
+        )
+    )
+)]
 pub async fn verify_lightweight_payment(
     payment_context: &PaymentContext,
     payment_header: &LightweightPaymentHeader,
     chain_state: &FacilitatorChainState,
 ) -> Result<LightweightVerifyResponse, MidenExactError> {
+    let result =
+        verify_lightweight_payment_inner(payment_context, payment_header, chain_state).await;
+
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        match &result {
+            Ok(_) => {
+                span.record("outcome", "verified");
+            }
+            Err(e) => {
+                span.record("outcome", "rejected");
+                span.record("error", tracing::field::display(e));
+            }
+        }
+    }
+
+    result
+}
+
+/// Shortens a hex account ID for tracing fields, falling back to the raw
+/// string if it does not parse.
+#[cfg(all(feature = "miden-native", feature = "tracing"))]
+fn short_account_id(hex: &str) -> String {
+    hex.parse::<crate::chain::MidenAccountAddress>()
+        .map(|address| address.short())
+        .unwrap_or_else(|_| hex.to_string())
+}
+
+#[cfg(feature = "miden-native")]
+async fn verify_lightweight_payment_inner(
+    payment_context: &PaymentContext,
+    payment_header: &LightweightPaymentHeader,
+    chain_state: &FacilitatorChainState,
+) -> Result<LightweightVerifyResponse, MidenExactError> {
+    use super::types::parse_fungible_faucet_id;
     use miden_protocol::Word;
-    use miden_protocol::account::AccountId;
     use miden_protocol::asset::FungibleAsset;
     use miden_protocol::crypto::merkle::SparseMerklePath;
-    use miden_protocol::note::{NoteId, NoteMetadata, compute_note_commitment};
+    use miden_protocol::note::{NoteId, NoteMetadata, NoteType, compute_note_commitment};
     use miden_protocol::utils::serde::Deserializable;
 
+    // ------------------------------------------------------------------
+    // 0. Reject structurally invalid headers before any hashing or RPC.
+    // ------------------------------------------------------------------
+    payment_header.validate(&PayloadLimits {
+        max_field_bytes: chain_state.max_header_field_bytes(),
+    })?;
+
     // ------------------------------------------------------------------
     // 1. Check that the payment context has not expired.
     // ------------------------------------------------------------------
-    if payment_context.is_expired(DEFAULT_PAYMENT_TIMEOUT_SECS) {
-        return Err(MidenExactError::TransactionExpired(
-            DEFAULT_PAYMENT_TIMEOUT_SECS,
-        ));
+    let expired_at = payment_context.expires_at(DEFAULT_PAYMENT_TIMEOUT_SECS);
+    let current = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock is before Unix epoch")
+        .as_secs();
+    if current >= expired_at {
+        return Err(MidenExactError::TransactionExpired {
+            expired_at,
+            current,
+        });
     }
 
     // ------------------------------------------------------------------
@@ -118,13 +200,8 @@ pub async fn verify_lightweight_payment(
         ))
     })?;
 
-    // 2b. Parse faucet account ID
-    let faucet_id = AccountId::from_hex(&payment_context.asset_faucet_id).map_err(|e| {
-        MidenExactError::DeserializationError(format!(
-            "Invalid faucet account ID '{}': {e}",
-            payment_context.asset_faucet_id
-        ))
-    })?;
+    // 2b. Parse faucet account ID and check that it is a fungible faucet
+    let faucet_id = parse_fungible_faucet_id(&payment_context.asset_faucet_id)?;
 
     // 2c. Compute asset commitment from FungibleAsset
     let asset = FungibleAsset::new(faucet_id, payment_context.amount).map_err(|e| {
@@ -152,6 +229,46 @@ pub async fn verify_lightweight_payment(
         });
     }
 
+    // ------------------------------------------------------------------
+    // 3b. Check the note tag.
+    //
+    //     The recipient's wallet discovers incoming notes by tag, so a note
+    //     carrying a different tag than the one issued to the agent may
+    //     never be found. The tag is bound by the metadata commitment in
+    //     the Merkle leaf, so checking it here (before any RPC) is sound.
+    // ------------------------------------------------------------------
+    let max_field_bytes = chain_state.max_header_field_bytes();
+
+    // Parse the note metadata from the agent's hex-encoded serialized NoteMetadata.
+    // The note metadata is also needed to compute the note commitment:
+    //   note_commitment = hash(note_id || metadata_commitment)
+    let metadata_bytes = decode_hex_field(
+        "note_metadata",
+        &payment_header.note_metadata,
+        max_field_bytes,
+    )?;
+
+    let note_metadata = NoteMetadata::read_from_bytes(&metadata_bytes).map_err(|e| {
+        MidenExactError::DeserializationError(format!("Failed to deserialize NoteMetadata: {e}"))
+    })?;
+
+    let note_tag = note_metadata.tag().as_u32();
+    if note_tag != payment_context.note_tag {
+        if chain_state.enforce_note_tag() {
+            return Err(MidenExactError::NoteTagMismatch {
+                expected: payment_context.note_tag,
+                got: note_tag,
+            });
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            note_id = %payment_header.note_id,
+            expected = payment_context.note_tag,
+            got = note_tag,
+            "Note tag mismatch ignored: recipient may not discover this note"
+        );
+    }
+
     // ------------------------------------------------------------------
     // 4. Get the block header from the chain state cache.
     //
@@ -168,14 +285,11 @@ pub async fn verify_lightweight_payment(
     //    The inclusion_proof is a hex-encoded SparseMerklePath that the
     //    agent obtained via sync_state() after transaction inclusion.
     // ------------------------------------------------------------------
-    let proof_hex = payment_header
-        .inclusion_proof
-        .strip_prefix("0x")
-        .unwrap_or(&payment_header.inclusion_proof);
-
-    let proof_bytes = hex::decode(proof_hex).map_err(|e| {
-        MidenExactError::DeserializationError(format!("Invalid hex in inclusion_proof: {e}"))
-    })?;
+    let proof_bytes = decode_hex_field(
+        "inclusion_proof",
+        &payment_header.inclusion_proof,
+        max_field_bytes,
+    )?;
 
     let merkle_path = SparseMerklePath::read_from_bytes(&proof_bytes).map_err(|e| {
         MidenExactError::DeserializationError(format!(
@@ -200,22 +314,6 @@ pub async fn verify_lightweight_payment(
         ))
     })?;
 
-    // Parse the note metadata from the agent's hex-encoded serialized NoteMetadata.
-    // The note metadata is needed to compute the note commitment:
-    //   note_commitment = hash(note_id || metadata_commitment)
-    let metadata_hex = payment_header
-        .note_metadata
-        .strip_prefix("0x")
-        .unwrap_or(&payment_header.note_metadata);
-
-    let metadata_bytes = hex::decode(metadata_hex).map_err(|e| {
-        MidenExactError::DeserializationError(format!("Invalid hex in note_metadata: {e}"))
-    })?;
-
-    let note_metadata = NoteMetadata::read_from_bytes(&metadata_bytes).map_err(|e| {
-        MidenExactError::DeserializationError(format!("Failed to deserialize NoteMetadata: {e}"))
-    })?;
-
     // Compute the note commitment, which is the leaf value stored in the
     // block's note tree at the position `note_index`.
     //   note_commitment = hash(note_id_word || metadata_commitment)
@@ -246,12 +344,31 @@ pub async fn verify_lightweight_payment(
 
     // ------------------------------------------------------------------
     // 6. Return success response.
+    //
+    //    The asset and amount are bound by the NoteId check; the sender
+    //    and note type are bound by the note commitment in the Merkle leaf,
+    //    which is returned as a receipt of what was checked.
     // ------------------------------------------------------------------
+    let note_type = if note_metadata.note_type() == NoteType::Public {
+        "public"
+    } else {
+        "private"
+    };
+
     Ok(LightweightVerifyResponse {
         valid: true,
         note_id: payment_header.note_id.clone(),
         block_num: payment_header.block_num,
         error: None,
+        details: Some(VerifiedNoteDetails {
+            note_commitment: note_commitment.to_hex(),
+            note_index: payment_header.note_index,
+            asset: payment_context.asset_faucet_id.clone(),
+            amount: payment_context.amount,
+            sender: note_metadata.sender().to_hex(),
+            note_type: note_type.to_string(),
+            note_tag,
+        }),
     })
 }
 
@@ -327,6 +444,25 @@ fn normalize_hex_string(s: &str) -> String {
     s.strip_prefix("0x").unwrap_or(s).to_lowercase()
 }
 
+/// Decodes a hex-encoded payment header field, with an optional `0x` prefix.
+///
+/// The size limit is checked before decoding so that oversized inputs are
+/// rejected without allocating for them. Other failures are classified as
+/// [`HexFieldError`](crate::v2_miden_exact::types::HexFieldError)s naming
+/// the field, so clients can tell a badly built payload apart from a
+/// facilitator problem.
+#[cfg(any(feature = "miden-native", test))]
+fn decode_hex_field(
+    field: &str,
+    value: &str,
+    max_bytes: usize,
+) -> Result<Vec<u8>, MidenExactError> {
+    super::types::check_hex_field(field, value, max_bytes)?;
+    let hex_str = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(hex_str)
+        .map_err(|e| MidenExactError::DeserializationError(format!("Invalid hex in {field}: {e}")))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -343,6 +479,104 @@ mod tests {
         assert_eq!(normalize_hex_string("abcdef"), "abcdef");
     }
 
+    #[test]
+    fn test_decode_hex_field_accepts_prefixed_and_bare() {
+        assert_eq!(
+            decode_hex_field("f", "0xcafe", 2).unwrap(),
+            vec![0xca, 0xfe]
+        );
+        assert_eq!(decode_hex_field("f", "CAFE", 2).unwrap(), vec![0xca, 0xfe]);
+    }
+
+    #[test]
+    fn test_decode_hex_field_size_limit() {
+        // Just under and exactly at the limit
+        assert_eq!(
+            decode_hex_field("f", &"ab".repeat(15), 16).unwrap().len(),
+            15
+        );
+        assert_eq!(
+            decode_hex_field("f", &"ab".repeat(16), 16).unwrap().len(),
+            16
+        );
+
+        // Just over the limit
+        let err = decode_hex_field("f", &"ab".repeat(17), 16).unwrap_err();
+        assert!(matches!(
+            err,
+            MidenExactError::PayloadTooLarge {
+                got: 17,
+                max: 16,
+                ..
+            }
+        ));
+
+        // Grossly over the limit, and not even valid hex: size is checked first
+        let err = decode_hex_field("f", &"zz".repeat(1_000_000), 16).unwrap_err();
+        assert!(matches!(
+            err,
+            MidenExactError::PayloadTooLarge {
+                got: 1_000_000,
+                max: 16,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_decode_hex_field_classifies_malformed_input() {
+        let cases = [
+            ("", HexFieldError::Empty),
+            ("0x", HexFieldError::Empty),
+            ("0xabc", HexFieldError::OddLength(3)),
+            (
+                "ABCD EF",
+                HexFieldError::NotHex {
+                    position: 4,
+                    character: ' ',
+                },
+            ),
+            (
+                "yv7+",
+                HexFieldError::NotHex {
+                    position: 0,
+                    character: 'y',
+                },
+            ),
+            (
+                "abzz",
+                HexFieldError::NotHex {
+                    position: 2,
+                    character: 'z',
+                },
+            ),
+        ];
+
+        for (input, expected) in cases {
+            match decode_hex_field("inclusion_proof", input, 16) {
+                Err(MidenExactError::MalformedField { field, reason }) => {
+                    assert_eq!(field, "inclusion_proof", "input {input:?}");
+                    assert_eq!(reason, expected, "input {input:?}");
+                }
+                other => panic!("input {input:?}: expected MalformedField, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_field_error_reports_field_and_reason() {
+        let err = decode_hex_field("note_metadata", "0xabc", 16).unwrap_err();
+        assert_eq!(err.field_error(), Some(("note_metadata", "odd_length")));
+
+        let err = decode_hex_field("note_metadata", "abcd", 1).unwrap_err();
+        assert_eq!(err.field_error(), Some(("note_metadata", "too_large")));
+
+        assert_eq!(
+            MidenExactError::ProviderError("down".into()).field_error(),
+            None
+        );
+    }
+
     #[test]
     fn test_normalize_hex_preserves_content() {
         let a = normalize_hex_string("0xDeAdBeEf");
@@ -363,6 +597,7 @@ mod tests {
             None,
         );
         let header = LightweightPaymentHeader {
+            payload_version: crate::lightweight::types::LIGHTWEIGHT_PAYLOAD_VERSION,
             note_id: "0xdeadbeef".to_string(),
             block_num: 10,
             note_index: 0,
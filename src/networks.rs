@@ -5,7 +5,9 @@
 
 use x402_types::chain::ChainId;
 
-use crate::chain::{MidenAccountAddress, MidenChainReference, MidenTokenDeployment};
+use crate::chain::{
+    MidenAccountAddress, MidenAddressParseError, MidenChainReference, MidenTokenDeployment,
+};
 
 /// Trait providing convenient methods for well-known Miden networks.
 ///
@@ -44,6 +46,12 @@ impl KnownNetworkMiden<ChainId> for ChainId {
 /// On Miden, USDC is represented as a fungible asset issued by a faucet account.
 pub struct MidenUSDC;
 
+/// Ticker symbol of the known USDC-equivalent deployments.
+const USDC_SYMBOL: &str = "USDC";
+
+/// Display name of the known USDC-equivalent deployments.
+const USDC_NAME: &str = "USD Coin";
+
 /// Environment variable name for overriding the testnet faucet ID at runtime.
 ///
 /// Set `MIDEN_TESTNET_FAUCET_ID=0x...` to use a custom faucet on testnet.
@@ -58,40 +66,87 @@ pub const TESTNET_FAUCET_ENV: &str = "MIDEN_TESTNET_FAUCET_ID";
 ///
 /// Note: This faucet ID may change across testnet resets. Override at runtime
 /// via the `MIDEN_TESTNET_FAUCET_ID` environment variable if needed.
-const DEFAULT_TESTNET_FAUCET_HEX: &str = "0x37d5977a8e16d8205a360820f0230f";
+const DEFAULT_TESTNET_FAUCET: MidenAccountAddress =
+    crate::miden_address!("0x37d5977a8e16d8205a360820f0230f");
 
 fn testnet_faucet_id() -> MidenAccountAddress {
     std::env::var(TESTNET_FAUCET_ENV)
         .ok()
         .and_then(|v| v.parse::<MidenAccountAddress>().ok())
-        .unwrap_or_else(|| {
-            DEFAULT_TESTNET_FAUCET_HEX
-                .parse()
-                .expect("default testnet faucet hex is valid")
-        })
+        .unwrap_or(DEFAULT_TESTNET_FAUCET)
+}
+
+/// Environment variable name for configuring the mainnet faucet ID at runtime.
+///
+/// Until a mainnet USDC faucet is published, mainnet deployments must set
+/// `MIDEN_MAINNET_FAUCET_ID=0x...` (or pass the faucet explicitly to
+/// [`MidenTokenDeployment::mainnet_usdc_with_faucet`]).
+pub const MAINNET_FAUCET_ENV: &str = "MIDEN_MAINNET_FAUCET_ID";
+
+/// Placeholder mainnet faucet ID, found in configurations written before a
+/// mainnet faucet was published.
+///
+/// All 0xFF bytes are an invalid account ID in Miden. It is refused wherever
+/// the mainnet faucet is resolved, rather than risk a silent mis-payment.
+const MAINNET_PLACEHOLDER_FAUCET_BYTES: [u8; 15] = [0xFF; 15];
+
+/// Error returned when no usable mainnet faucet is configured.
+#[derive(Debug, thiserror::Error)]
+pub enum MainnetFaucetError {
+    /// Neither `MIDEN_MAINNET_FAUCET_ID` nor an explicit faucet was provided,
+    /// or the configured faucet is the placeholder.
+    #[error(
+        "No mainnet faucet configured: set {MAINNET_FAUCET_ENV} or pass the faucet ID explicitly"
+    )]
+    Unconfigured,
+    /// `MIDEN_MAINNET_FAUCET_ID` is set but is not a valid account ID.
+    #[error("Invalid {MAINNET_FAUCET_ENV}: {0}")]
+    InvalidFaucetId(#[from] MidenAddressParseError),
+}
+
+/// Returns `true` if `faucet_id` is the mainnet placeholder faucet.
+pub fn is_mainnet_placeholder_faucet(faucet_id: &MidenAccountAddress) -> bool {
+    faucet_id.as_bytes() == &MAINNET_PLACEHOLDER_FAUCET_BYTES
+}
+
+#[cfg(test)]
+fn mainnet_placeholder_faucet_id() -> MidenAccountAddress {
+    MidenAccountAddress::from_bytes_unchecked(&MAINNET_PLACEHOLDER_FAUCET_BYTES)
+        .expect("placeholder is 15 bytes long")
+}
+
+/// Resolves the mainnet faucet from the value of `MIDEN_MAINNET_FAUCET_ID`.
+fn resolve_mainnet_faucet_id(
+    configured: Option<String>,
+) -> Result<MidenAccountAddress, MainnetFaucetError> {
+    let faucet_id =
+        MidenAccountAddress::parse_unchecked(&configured.ok_or(MainnetFaucetError::Unconfigured)?)?;
+    if is_mainnet_placeholder_faucet(&faucet_id) {
+        return Err(MainnetFaucetError::Unconfigured);
+    }
+    faucet_id.validate()?;
+    Ok(faucet_id)
 }
 
 impl KnownNetworkMiden<MidenTokenDeployment> for MidenUSDC {
     fn miden_testnet() -> MidenTokenDeployment {
-        MidenTokenDeployment {
-            chain_reference: MidenChainReference::testnet(),
-            faucet_id: testnet_faucet_id(),
-            decimals: 6,
-        }
+        MidenTokenDeployment::new(MidenChainReference::testnet(), testnet_faucet_id(), 6)
+            .with_symbol(USDC_SYMBOL)
+            .with_name(USDC_NAME)
     }
 
+    /// Returns the mainnet USDC deployment.
+    ///
+    /// Uses the faucet in `MIDEN_MAINNET_FAUCET_ID`, since no mainnet faucet
+    /// has been published yet. Prefer [`MidenTokenDeployment::mainnet_usdc`],
+    /// which reports a missing faucet as an error instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the [`MainnetFaucetError`] message if the variable is
+    /// unset, holds the placeholder faucet or is not a valid account ID.
     fn miden_mainnet() -> MidenTokenDeployment {
-        MidenTokenDeployment {
-            chain_reference: MidenChainReference::mainnet(),
-            // Mainnet faucet ID will be set at mainnet launch.
-            // Using a distinctive placeholder that will fail loudly if accidentally used.
-            // All 0xFF bytes are an invalid account ID in Miden, so any attempt to
-            // transact with this placeholder will produce an immediate error rather
-            // than a silent mis-payment.
-            faucet_id: MidenAccountAddress::from_bytes(&[0xFF; 15])
-                .expect("15-byte placeholder is always valid"),
-            decimals: 6,
-        }
+        MidenTokenDeployment::mainnet_usdc().unwrap_or_else(|error| panic!("{error}"))
     }
 }
 
@@ -104,11 +159,90 @@ impl MidenTokenDeployment {
         MidenUSDC::miden_testnet()
     }
 
-    /// Convenience alias for [`MidenUSDC::miden_mainnet()`].
+    /// Returns the mainnet USDC-equivalent token deployment.
+    ///
+    /// The faucet ID is read from `MIDEN_MAINNET_FAUCET_ID`, since no mainnet
+    /// faucet has been published yet.
+    ///
+    /// # Errors
     ///
-    /// Returns a mainnet USDC-equivalent token deployment. Delegates to
-    /// [`KnownNetworkMiden::miden_mainnet()`] on [`MidenUSDC`].
-    pub fn mainnet_usdc() -> Self {
-        MidenUSDC::miden_mainnet()
+    /// Returns [`MainnetFaucetError::Unconfigured`] if the variable is unset or
+    /// holds the placeholder faucet, and
+    /// [`MainnetFaucetError::InvalidFaucetId`] if it is not a valid account ID.
+    pub fn mainnet_usdc() -> Result<Self, MainnetFaucetError> {
+        let faucet_id = resolve_mainnet_faucet_id(std::env::var(MAINNET_FAUCET_ENV).ok())?;
+        Ok(Self::mainnet_usdc_with_faucet(faucet_id))
+    }
+
+    /// Returns the mainnet USDC-equivalent token deployment for an explicitly
+    /// configured faucet.
+    pub fn mainnet_usdc_with_faucet(faucet_id: MidenAccountAddress) -> Self {
+        MidenTokenDeployment::new(MidenChainReference::mainnet(), faucet_id, 6)
+            .with_symbol(USDC_SYMBOL)
+            .with_name(USDC_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_mainnet_faucet_unset() {
+        assert!(matches!(
+            resolve_mainnet_faucet_id(None),
+            Err(MainnetFaucetError::Unconfigured)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_mainnet_faucet_placeholder() {
+        let placeholder = format!("0x{}", "ff".repeat(15));
+        assert!(matches!(
+            resolve_mainnet_faucet_id(Some(placeholder)),
+            Err(MainnetFaucetError::Unconfigured)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_mainnet_faucet_invalid() {
+        assert!(matches!(
+            resolve_mainnet_faucet_id(Some("not-a-faucet".to_string())),
+            Err(MainnetFaucetError::InvalidFaucetId(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_mainnet_faucet_override() {
+        let faucet = resolve_mainnet_faucet_id(Some(DEFAULT_TESTNET_FAUCET.to_hex()))
+            .expect("valid faucet should be accepted");
+        assert_eq!(faucet, DEFAULT_TESTNET_FAUCET);
+        assert!(!is_mainnet_placeholder_faucet(&faucet));
+    }
+
+    #[test]
+    fn test_known_deployments_carry_metadata() {
+        let testnet = MidenTokenDeployment::testnet_usdc();
+        assert_eq!(testnet.symbol.as_deref(), Some("USDC"));
+        assert_eq!(testnet.name.as_deref(), Some("USD Coin"));
+        assert_eq!(testnet.amount(1_500_000).to_string(), "1.5 USDC");
+
+        let faucet = resolve_mainnet_faucet_id(Some(DEFAULT_TESTNET_FAUCET.to_hex())).unwrap();
+        let mainnet = MidenTokenDeployment::mainnet_usdc_with_faucet(faucet);
+        assert_eq!(mainnet.symbol.as_deref(), Some("USDC"));
+    }
+
+    #[test]
+    fn test_default_testnet_faucet_matches_runtime_parse() {
+        let parsed: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        assert_eq!(DEFAULT_TESTNET_FAUCET, parsed);
+        assert!(DEFAULT_TESTNET_FAUCET.validate().is_ok());
+    }
+
+    #[test]
+    fn test_placeholder_detection() {
+        assert!(is_mainnet_placeholder_faucet(
+            &mainnet_placeholder_faucet_id()
+        ));
     }
 }
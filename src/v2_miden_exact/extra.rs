@@ -0,0 +1,236 @@
+//! Typed view of the `extra` field of V2 Miden payment requirements.
+//!
+//! The x402 wire format leaves `extra` as free-form JSON. Miden uses it for
+//! token metadata, note parameters and fees; [`MidenRequirementsExtra`]
+//! gives those entries types, and keeps any others in
+//! [`other`](MidenRequirementsExtra::other) so they survive a round trip.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use x402_chain_miden::v2_miden_exact::{MidenPaymentRequirementsExt, MidenRequirementsExtra};
+//!
+//! let extra = requirements.miden_extra()?;
+//! if let Some(symbol) = &extra.symbol {
+//!     println!("Paying in {symbol}");
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use x402_types::proto::v2;
+
+use super::MidenExactError;
+
+/// Miden-specific entries of [`v2::PaymentRequirements::extra`].
+///
+/// Every field is optional and omitted from the JSON when unset. Entries
+/// without a typed field are kept in [`other`](Self::other).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidenRequirementsExtra {
+    /// Ticker symbol of the asset, e.g. `"USDC"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Human-readable asset name, e.g. `"USD Coin"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Decimal places of the asset, needed to render `amount`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    /// Whether the payment note must be public or may be private.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_type: Option<MidenNoteType>,
+    /// Tag the payment note must carry so the recipient can discover it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_tag: Option<u32>,
+    /// Fee charged by the facilitator on top of `amount`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facilitator_fee: Option<MidenFacilitatorFee>,
+    /// Further recipients the payment is split across.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub splits: Vec<MidenPaymentSplit>,
+    /// Entries without a typed field, preserved as-is.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Visibility of a payment note on the Miden network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MidenNoteType {
+    /// The note's details are published on chain.
+    Public,
+    /// Only the note's commitment is published on chain.
+    Private,
+}
+
+/// A fee paid to the facilitator, in base units of the requirement's asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidenFacilitatorFee {
+    /// The account receiving the fee.
+    pub pay_to: String,
+    /// The fee in the asset's smallest unit.
+    pub amount: String,
+}
+
+/// A share of a payment sent to an additional recipient, in base units of
+/// the requirement's asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidenPaymentSplit {
+    /// The account receiving this share.
+    pub pay_to: String,
+    /// The share in the asset's smallest unit.
+    pub amount: String,
+}
+
+impl MidenRequirementsExtra {
+    /// Returns `true` if no entry is set, typed or not.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Typed access to the Miden entries of [`v2::PaymentRequirements::extra`].
+pub trait MidenPaymentRequirementsExt: Sized {
+    /// Parses `extra`, returning an empty [`MidenRequirementsExtra`] when it
+    /// is absent or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenExactError::DeserializationError`] if `extra` is not an
+    /// object, or a typed entry has the wrong type.
+    fn miden_extra(&self) -> Result<MidenRequirementsExtra, MidenExactError>;
+
+    /// Replaces `extra`, leaving it unset when `extra` is empty.
+    fn with_miden_extra(self, extra: MidenRequirementsExtra) -> Self;
+}
+
+impl MidenPaymentRequirementsExt for v2::PaymentRequirements {
+    fn miden_extra(&self) -> Result<MidenRequirementsExtra, MidenExactError> {
+        match &self.extra {
+            None | Some(serde_json::Value::Null) => Ok(MidenRequirementsExtra::default()),
+            Some(value) => serde_json::from_value(value.clone()).map_err(|e| {
+                MidenExactError::DeserializationError(format!("invalid requirements extra: {e}"))
+            }),
+        }
+    }
+
+    fn with_miden_extra(mut self, extra: MidenRequirementsExtra) -> Self {
+        self.extra = if extra.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_value(extra).expect("extra serializes to JSON"))
+        };
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn requirements(extra: Option<serde_json::Value>) -> v2::PaymentRequirements {
+        v2::PaymentRequirements {
+            scheme: "exact".to_string(),
+            network: crate::chain::MidenChainReference::testnet().into(),
+            amount: "1000000".to_string(),
+            pay_to: "0xaabbccddeeff00102233aabbccddee".to_string(),
+            max_timeout_seconds: 300,
+            asset: "0x37d5977a8e16d8205a360820f0230f".to_string(),
+            extra,
+        }
+    }
+
+    #[test]
+    fn test_full_extra_roundtrip() {
+        let json = json!({
+            "symbol": "USDC",
+            "name": "USD Coin",
+            "decimals": 6,
+            "noteType": "private",
+            "noteTag": 42,
+            "facilitatorFee": {"payTo": "0xdeadbeef0102030005060708090a0b", "amount": "1000"},
+            "splits": [{"payTo": "0xabcdef12345678902bcdef12345678", "amount": "250000"}],
+        });
+        let extra: MidenRequirementsExtra = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(extra.symbol.as_deref(), Some("USDC"));
+        assert_eq!(extra.name.as_deref(), Some("USD Coin"));
+        assert_eq!(extra.decimals, Some(6));
+        assert_eq!(extra.note_type, Some(MidenNoteType::Private));
+        assert_eq!(extra.note_tag, Some(42));
+        assert_eq!(extra.facilitator_fee.as_ref().unwrap().amount, "1000");
+        assert_eq!(extra.splits[0].amount, "250000");
+        assert!(extra.other.is_empty());
+        assert_eq!(serde_json::to_value(&extra).unwrap(), json);
+    }
+
+    #[test]
+    fn test_empty_extra_serializes_to_empty_object() {
+        let extra = MidenRequirementsExtra::default();
+        assert!(extra.is_empty());
+        assert_eq!(serde_json::to_value(&extra).unwrap(), json!({}));
+        let decoded: MidenRequirementsExtra = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(decoded, extra);
+    }
+
+    #[test]
+    fn test_unknown_fields_preserved() {
+        let json = json!({
+            "symbol": "USDC",
+            "futureField": {"nested": [1, 2, 3]},
+            "vendor": "acme",
+        });
+        let extra: MidenRequirementsExtra = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(extra.symbol.as_deref(), Some("USDC"));
+        assert_eq!(extra.other.len(), 2);
+        assert_eq!(extra.other["vendor"], "acme");
+        assert!(!extra.is_empty());
+        assert_eq!(serde_json::to_value(&extra).unwrap(), json);
+    }
+
+    #[test]
+    fn test_wrongly_typed_entries_rejected() {
+        for json in [
+            json!({"decimals": "six"}),
+            json!({"decimals": 256}),
+            json!({"noteType": "secret"}),
+            json!({"noteTag": -1}),
+            json!({"facilitatorFee": {"payTo": "0x00"}}),
+            json!({"splits": {}}),
+        ] {
+            assert!(
+                serde_json::from_value::<MidenRequirementsExtra>(json.clone()).is_err(),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_requirements_helpers() {
+        assert!(requirements(None).miden_extra().unwrap().is_empty());
+        assert!(
+            requirements(Some(serde_json::Value::Null))
+                .miden_extra()
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            requirements(Some(json!([1, 2]))).miden_extra(),
+            Err(MidenExactError::DeserializationError(_))
+        ));
+
+        let extra = MidenRequirementsExtra {
+            note_tag: Some(7),
+            ..Default::default()
+        };
+        let with_extra = requirements(None).with_miden_extra(extra.clone());
+        assert_eq!(with_extra.extra, Some(json!({"noteTag": 7})));
+        assert_eq!(with_extra.miden_extra().unwrap(), extra);
+
+        let cleared = with_extra.with_miden_extra(MidenRequirementsExtra::default());
+        assert!(cleared.extra.is_none());
+    }
+}
@@ -29,6 +29,9 @@
 #[cfg(feature = "server")]
 pub mod server;
 
+pub mod extra;
+pub use extra::*;
+
 pub mod types;
 pub use types::*;
 
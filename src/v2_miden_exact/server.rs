@@ -20,8 +20,13 @@ use x402_types::chain::ChainId;
 use x402_types::proto::v2;
 
 use crate::V2MidenExact;
-use crate::chain::{MidenAccountAddress, MidenDeployedTokenAmount};
+use crate::chain::{
+    MidenAccountAddress, MidenChainReference, MidenDeployedTokenAmount, MidenTokenDeployment,
+    MidenTokenRegistry, MidenTokenRegistryError,
+};
 use crate::v2_miden_exact::ExactScheme;
+use crate::v2_miden_exact::MidenExactError;
+use crate::v2_miden_exact::{MidenPaymentRequirementsExt, MidenRequirementsExtra};
 
 impl V2MidenExact {
     /// Creates a V2 price tag for a Miden payment.
@@ -35,6 +40,10 @@ impl V2MidenExact {
     /// - `pay_to`: The recipient's Miden account address
     /// - `asset`: The token deployment and amount required
     ///
+    /// When the token deployment has a symbol or name, they are sent in the
+    /// requirements' `extra` together with the decimals, so clients can
+    /// render the price (e.g. `1.5 USDC`).
+    ///
     /// # Returns
     ///
     /// A [`v2::PriceTag`] that can be included in a `PaymentRequired` response.
@@ -48,10 +57,69 @@ impl V2MidenExact {
             amount: asset.amount.to_string(),
             max_timeout_seconds: 300,
             extra: None,
-        };
+        }
+        .with_miden_extra(token_metadata_extra(&asset.token));
         v2::PriceTag {
             requirements,
             enricher: None,
         }
     }
+
+    /// Creates a V2 price tag after checking that the asset is a fungible faucet.
+    ///
+    /// Same as [`price_tag`](Self::price_tag), but rejects a token deployment
+    /// whose `faucet_id` is not a fungible faucet account (e.g. a regular
+    /// wallet ID pasted by mistake), which would otherwise only surface when
+    /// the first payment fails verification.
+    ///
+    /// The check reads the account type bits of the ID, so it works without
+    /// the `miden-native` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenExactError::InvalidFaucet`] if the asset is not a
+    /// fungible faucet.
+    pub fn try_price_tag(
+        pay_to: MidenAccountAddress,
+        asset: MidenDeployedTokenAmount,
+    ) -> Result<v2::PriceTag, MidenExactError> {
+        crate::lightweight::types::check_fungible_faucet(&asset.token.faucet_id)?;
+        Ok(Self::price_tag(pay_to, asset))
+    }
+
+    /// Creates a V2 price tag for a whole-token `amount` (e.g. `"1.50"`) of
+    /// the token registered as `symbol` on `network`.
+    ///
+    /// Same as [`price_tag`](Self::price_tag), with the deployment and its
+    /// decimals taken from `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MidenTokenRegistryError::UnknownToken`] if no such token is
+    /// registered, and [`MidenTokenRegistryError::Amount`] if the amount does
+    /// not parse.
+    pub fn price_tag_from_registry(
+        registry: &MidenTokenRegistry,
+        pay_to: MidenAccountAddress,
+        network: &MidenChainReference,
+        symbol: &str,
+        amount: &str,
+    ) -> Result<v2::PriceTag, MidenTokenRegistryError> {
+        let asset = registry.parse_amount(network, symbol, amount)?;
+        Ok(Self::price_tag(pay_to, asset))
+    }
+}
+
+/// Builds the `extra` entries describing the token, left empty if the
+/// deployment has neither symbol nor name.
+fn token_metadata_extra(token: &MidenTokenDeployment) -> MidenRequirementsExtra {
+    if token.symbol.is_none() && token.name.is_none() {
+        return MidenRequirementsExtra::default();
+    }
+    MidenRequirementsExtra {
+        symbol: token.symbol.clone(),
+        name: token.name.clone(),
+        decimals: Some(token.decimals),
+        ..Default::default()
+    }
 }
@@ -58,8 +58,11 @@ pub enum MidenExactError {
     PaymentNotFound(String),
 
     /// The payment context or transaction has expired.
-    #[error("Transaction expired at block {0}")]
-    TransactionExpired(u64),
+    ///
+    /// Both times are Unix timestamps in seconds. Clients should request a
+    /// fresh payment requirement rather than retry the stale payment.
+    #[error("Payment expired at {expired_at}, current time is {current} (Unix seconds)")]
+    TransactionExpired { expired_at: u64, current: u64 },
 
     /// Failed to deserialize data.
     #[error("Deserialization error: {0}")]
@@ -69,32 +72,138 @@ pub enum MidenExactError {
     #[error("Provider error: {0}")]
     ProviderError(String),
 
+    /// The asset account ID is not a fungible faucet.
+    #[error("Invalid faucet: {0}")]
+    InvalidFaucet(String),
+
+    /// A hex-encoded payload field decodes to more bytes than allowed.
+    #[error("Payload too large: {field} is {got} bytes, exceeding the maximum of {max}")]
+    PayloadTooLarge {
+        field: String,
+        got: usize,
+        max: usize,
+    },
+
+    /// A hex-encoded payload field is not valid hex.
+    #[error("Malformed {field}: {reason}")]
+    MalformedField {
+        field: String,
+        reason: HexFieldError,
+    },
+
     // --- Lightweight verification errors (bobbinth's design, 0xMiden/node#1796) ---
     /// The note ID does not match the expected value computed from
     /// `hash(recipient_digest, asset_commitment)`.
     #[error("NoteId mismatch: expected {expected}, got {got}")]
     NoteIdMismatch { expected: String, got: String },
 
+    /// The note's tag differs from the one issued in the payment requirement,
+    /// so the recipient's wallet may never discover the note.
+    #[error("Note tag mismatch: expected {expected}, got {got}")]
+    NoteTagMismatch { expected: u32, got: u32 },
+
     /// The Merkle inclusion proof (SparseMerklePath) is invalid or does
     /// not verify against the block's note commitment root.
     #[error("Invalid inclusion proof: {0}")]
     InclusionProofInvalid(String),
+
+    /// The payment header uses a payload format version this facilitator
+    /// does not understand; the client must be updated (or downgraded).
+    #[error("Unsupported payload version {got}, this facilitator supports version {supported}")]
+    UnsupportedPayloadVersion { got: u8, supported: u8 },
+}
+
+impl MidenExactError {
+    /// Returns `true` if the error means the payment itself is invalid.
+    ///
+    /// Verification errors are the client's fault (wrong amount, bad proof,
+    /// expired payment) and should be reported as a 4xx. Everything else is
+    /// a facilitator or RPC problem and should be reported as a 5xx.
+    pub fn is_verification_error(&self) -> bool {
+        match self {
+            MidenExactError::InvalidProof(_)
+            | MidenExactError::PaymentNotFound(_)
+            | MidenExactError::TransactionExpired { .. }
+            | MidenExactError::DeserializationError(_)
+            | MidenExactError::InvalidFaucet(_)
+            | MidenExactError::PayloadTooLarge { .. }
+            | MidenExactError::MalformedField { .. }
+            | MidenExactError::NoteIdMismatch { .. }
+            | MidenExactError::NoteTagMismatch { .. }
+            | MidenExactError::InclusionProofInvalid(_)
+            | MidenExactError::UnsupportedPayloadVersion { .. } => true,
+            MidenExactError::ProviderError(_) => false,
+        }
+    }
+
+    /// Returns the offending field and a machine-readable reason code if the
+    /// error is about a malformed payload field.
+    ///
+    /// Reason codes are `empty`, `not_hex`, `odd_length`, `wrong_length` and
+    /// `too_large`.
+    pub fn field_error(&self) -> Option<(&str, &'static str)> {
+        match self {
+            MidenExactError::PayloadTooLarge { field, .. } => Some((field, "too_large")),
+            MidenExactError::MalformedField { field, reason } => Some((field, reason.code())),
+            _ => None,
+        }
+    }
+}
+
+/// Why a hex-encoded payload field could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HexFieldError {
+    /// The field is empty (or only a `0x` prefix).
+    #[error("field is empty")]
+    Empty,
+    /// The field contains a character that is not a hex digit.
+    #[error("non-hex character {character:?} at position {position}")]
+    NotHex { position: usize, character: char },
+    /// The field has an odd number of hex digits.
+    #[error("odd hex length {0}")]
+    OddLength(usize),
+    /// The field decodes to the wrong number of bytes for its type.
+    #[error("expected {expected} bytes, got {got}")]
+    WrongLength { expected: usize, got: usize },
+}
+
+impl HexFieldError {
+    /// Returns a machine-readable code for this reason.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HexFieldError::Empty => "empty",
+            HexFieldError::NotHex { .. } => "not_hex",
+            HexFieldError::OddLength(_) => "odd_length",
+            HexFieldError::WrongLength { .. } => "wrong_length",
+        }
+    }
 }
 
 impl From<MidenExactError> for x402_types::scheme::X402SchemeFacilitatorError {
     fn from(value: MidenExactError) -> Self {
-        match value {
-            MidenExactError::NoteIdMismatch { expected, got } => {
-                x402_types::scheme::X402SchemeFacilitatorError::PaymentVerification(
-                    x402_types::proto::PaymentVerificationError::InvalidFormat(format!(
-                        "NoteId mismatch: expected {expected}, got {got}"
-                    )),
-                )
+        use x402_types::proto::PaymentVerificationError;
+        use x402_types::scheme::X402SchemeFacilitatorError;
+
+        let verification_error = match value {
+            MidenExactError::InvalidProof(_) | MidenExactError::InclusionProofInvalid(_) => {
+                PaymentVerificationError::InvalidSignature(value.to_string())
             }
-            other => {
-                x402_types::scheme::X402SchemeFacilitatorError::OnchainFailure(other.to_string())
+            MidenExactError::PaymentNotFound(_)
+            | MidenExactError::DeserializationError(_)
+            | MidenExactError::PayloadTooLarge { .. }
+            | MidenExactError::MalformedField { .. }
+            | MidenExactError::NoteIdMismatch { .. }
+            | MidenExactError::UnsupportedPayloadVersion { .. } => {
+                PaymentVerificationError::InvalidFormat(value.to_string())
             }
-        }
+            MidenExactError::TransactionExpired { .. } => PaymentVerificationError::Expired,
+            MidenExactError::InvalidFaucet(_) => PaymentVerificationError::AssetMismatch,
+            MidenExactError::NoteTagMismatch { .. } => PaymentVerificationError::RecipientMismatch,
+            MidenExactError::ProviderError(_) => {
+                return X402SchemeFacilitatorError::OnchainFailure(value.to_string());
+            }
+        };
+        X402SchemeFacilitatorError::PaymentVerification(verification_error)
     }
 }
 
@@ -114,4 +223,88 @@ mod tests {
         let deserialized: ExactScheme = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.to_string(), "exact");
     }
+
+    #[test]
+    fn test_error_to_facilitator_error_mapping() {
+        use x402_types::proto::PaymentVerificationError as V;
+        use x402_types::scheme::X402SchemeFacilitatorError as F;
+
+        type Expectation = fn(&F) -> bool;
+
+        let cases: Vec<(MidenExactError, Expectation)> = vec![
+            (MidenExactError::InvalidProof("bad".into()), |e| {
+                matches!(e, F::PaymentVerification(V::InvalidSignature(_)))
+            }),
+            (MidenExactError::PaymentNotFound("none".into()), |e| {
+                matches!(e, F::PaymentVerification(V::InvalidFormat(_)))
+            }),
+            (
+                MidenExactError::TransactionExpired {
+                    expired_at: 42,
+                    current: 43,
+                },
+                |e| matches!(e, F::PaymentVerification(V::Expired)),
+            ),
+            (MidenExactError::DeserializationError("hex".into()), |e| {
+                matches!(e, F::PaymentVerification(V::InvalidFormat(_)))
+            }),
+            (MidenExactError::ProviderError("rpc down".into()), |e| {
+                matches!(e, F::OnchainFailure(_))
+            }),
+            (MidenExactError::InvalidFaucet("wallet".into()), |e| {
+                matches!(e, F::PaymentVerification(V::AssetMismatch))
+            }),
+            (
+                MidenExactError::PayloadTooLarge {
+                    field: "inclusion_proof".into(),
+                    got: 2,
+                    max: 1,
+                },
+                |e| matches!(e, F::PaymentVerification(V::InvalidFormat(_))),
+            ),
+            (
+                MidenExactError::MalformedField {
+                    field: "note_metadata".into(),
+                    reason: HexFieldError::OddLength(3),
+                },
+                |e| matches!(e, F::PaymentVerification(V::InvalidFormat(_))),
+            ),
+            (
+                MidenExactError::NoteIdMismatch {
+                    expected: "0xaa".into(),
+                    got: "0xbb".into(),
+                },
+                |e| matches!(e, F::PaymentVerification(V::InvalidFormat(_))),
+            ),
+            (
+                MidenExactError::NoteTagMismatch {
+                    expected: 1,
+                    got: 2,
+                },
+                |e| matches!(e, F::PaymentVerification(V::RecipientMismatch)),
+            ),
+            (MidenExactError::InclusionProofInvalid("root".into()), |e| {
+                matches!(e, F::PaymentVerification(V::InvalidSignature(_)))
+            }),
+            (
+                MidenExactError::UnsupportedPayloadVersion {
+                    got: 2,
+                    supported: 1,
+                },
+                |e| matches!(e, F::PaymentVerification(V::InvalidFormat(_))),
+            ),
+        ];
+
+        for (error, expected) in cases {
+            let description = error.to_string();
+            let is_verification = error.is_verification_error();
+            let mapped = F::from(error);
+            assert!(expected(&mapped), "unexpected mapping for '{description}'");
+            assert_eq!(
+                is_verification,
+                matches!(mapped, F::PaymentVerification(_)),
+                "is_verification_error disagrees with mapping for '{description}'"
+            );
+        }
+    }
 }
@@ -68,10 +68,10 @@ async fn build_testnet_client() -> Client<FilesystemKeyStore> {
 async fn e2e_get_account_balance() {
     println!("\n=== Balance Query Test ===\n");
 
-    let config = MidenChainConfig {
-        chain_reference: MidenChainReference::testnet(),
-        rpc_url: "https://rpc.testnet.miden.io".to_string(),
-    };
+    let config = MidenChainConfig::new(
+        MidenChainReference::testnet(),
+        "https://rpc.testnet.miden.io".parse().unwrap(),
+    );
     let provider = MidenChainProvider::from_config(&config);
 
     let balance = provider
@@ -102,8 +102,8 @@ async fn e2e_account_id_roundtrip() {
     use miden_protocol::account::AccountId;
 
     let addr: MidenAccountAddress = WALLET_1.parse().expect("parse wallet 1");
-    let account_id = addr.to_account_id().expect("convert to AccountId");
-    let addr2 = MidenAccountAddress::from_account_id(account_id);
+    let account_id = AccountId::try_from(&addr).expect("convert to AccountId");
+    let addr2 = MidenAccountAddress::from(account_id);
     assert_eq!(addr, addr2, "roundtrip should preserve address");
 
     // Also verify it matches what from_hex gives
@@ -50,6 +50,18 @@ fn test_usdc_testnet_deployment() {
 
 #[test]
 fn test_usdc_mainnet_deployment() {
+    use x402_chain_miden::MAINNET_FAUCET_ENV;
+
+    // Without a configured faucet there is no mainnet deployment to return.
+    if std::env::var(MAINNET_FAUCET_ENV).is_err() {
+        let panic = std::panic::catch_unwind(MidenUSDC::miden_mainnet).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("No mainnet faucet configured"),
+            "{message}"
+        );
+        return;
+    }
     let usdc = MidenUSDC::miden_mainnet();
     assert_eq!(usdc.decimals, 6);
     assert_eq!(usdc.chain_reference, MidenChainReference::mainnet());
@@ -58,13 +70,38 @@ fn test_usdc_mainnet_deployment() {
 #[test]
 fn test_token_deployment_convenience() {
     let testnet = MidenTokenDeployment::testnet_usdc();
-    let mainnet = MidenTokenDeployment::mainnet_usdc();
+    let faucet: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+    let mainnet = MidenTokenDeployment::mainnet_usdc_with_faucet(faucet);
     assert_eq!(testnet.chain_reference, MidenChainReference::testnet());
     assert_eq!(mainnet.chain_reference, MidenChainReference::mainnet());
     assert_eq!(testnet.decimals, 6);
     assert_eq!(mainnet.decimals, 6);
 }
 
+#[test]
+fn test_mainnet_usdc_requires_configured_faucet() {
+    use x402_chain_miden::{MAINNET_FAUCET_ENV, MainnetFaucetError};
+
+    // Only meaningful when the environment does not configure a mainnet faucet.
+    if std::env::var(MAINNET_FAUCET_ENV).is_err() {
+        assert!(matches!(
+            MidenTokenDeployment::mainnet_usdc(),
+            Err(MainnetFaucetError::Unconfigured)
+        ));
+    }
+}
+
+#[test]
+fn test_mainnet_placeholder_faucet_detected() {
+    use x402_chain_miden::is_mainnet_placeholder_faucet;
+
+    let placeholder = MidenAccountAddress::from_bytes_unchecked(&[0xFF; 15]).unwrap();
+    assert!(is_mainnet_placeholder_faucet(&placeholder));
+
+    let testnet = MidenTokenDeployment::testnet_usdc();
+    assert!(!is_mainnet_placeholder_faucet(&testnet.faucet_id));
+}
+
 // ============================================================================
 // Chain Reference Tests
 // ============================================================================
@@ -79,6 +116,7 @@ fn test_chain_reference_try_from_str() {
 }
 
 #[test]
+#[cfg(not(feature = "custom-networks"))]
 fn test_chain_reference_try_from_str_invalid() {
     let result = MidenChainReference::try_from("devnet");
     assert!(result.is_err());
@@ -98,19 +136,19 @@ fn test_chain_reference_roundtrip_via_chain_id() {
 
 #[test]
 fn test_miden_address_parse_hex() {
-    let addr: MidenAccountAddress = "0xaabbccddeeff00112233aabbccddee".parse().unwrap();
+    let addr: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
     assert!(addr.to_string().starts_with("0x"));
 }
 
 #[test]
 fn test_miden_address_parse_no_prefix() {
-    let addr: MidenAccountAddress = "aabbccddeeff00112233aabbccddee".parse().unwrap();
+    let addr: MidenAccountAddress = "aabbccddeeff00102233aabbccddee".parse().unwrap();
     assert!(addr.to_string().starts_with("0x"));
 }
 
 #[test]
 fn test_miden_address_roundtrip() {
-    let original: MidenAccountAddress = "0xdeadbeef0102030405060708090a0b".parse().unwrap();
+    let original: MidenAccountAddress = "0xdeadbeef0102030005060708090a0b".parse().unwrap();
     let s = original.to_string();
     let recovered: MidenAccountAddress = s.parse().unwrap();
     assert_eq!(original, recovered);
@@ -118,7 +156,7 @@ fn test_miden_address_roundtrip() {
 
 #[test]
 fn test_miden_address_serde_json() {
-    let addr: MidenAccountAddress = "0xdeadbeef0102030405060708090a0b".parse().unwrap();
+    let addr: MidenAccountAddress = "0xdeadbeef0102030005060708090a0b".parse().unwrap();
     let json = serde_json::to_string(&addr).unwrap();
     let recovered: MidenAccountAddress = serde_json::from_str(&json).unwrap();
     assert_eq!(addr, recovered);
@@ -167,7 +205,7 @@ mod server_tests {
 
     #[test]
     fn test_price_tag_creation() {
-        let recipient: MidenAccountAddress = "0xaabbccddeeff00112233aabbccddee".parse().unwrap();
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
         let usdc = MidenTokenDeployment::testnet_usdc();
         let price_tag = V2MidenExact::price_tag(recipient.clone(), usdc.amount(1_000_000));
 
@@ -179,19 +217,101 @@ mod server_tests {
         assert!(price_tag.enricher.is_none());
     }
 
+    #[test]
+    fn test_price_tag_from_registry() {
+        use x402_chain_miden::chain::{MidenTokenRegistry, MidenTokenRegistryError};
+
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        let registry = MidenTokenRegistry::known();
+        let testnet = MidenChainReference::testnet();
+
+        let price_tag = V2MidenExact::price_tag_from_registry(
+            &registry,
+            recipient.clone(),
+            &testnet,
+            "usdc",
+            "1.50",
+        )
+        .unwrap();
+        assert_eq!(price_tag.requirements.amount, "1500000");
+        assert_eq!(
+            price_tag.requirements.asset,
+            MidenTokenDeployment::testnet_usdc().faucet_id.to_string()
+        );
+        assert_eq!(price_tag.requirements.extra.unwrap()["symbol"], "USDC");
+
+        assert!(matches!(
+            V2MidenExact::price_tag_from_registry(
+                &registry,
+                recipient.clone(),
+                &testnet,
+                "DAI",
+                "1"
+            ),
+            Err(MidenTokenRegistryError::UnknownToken { .. })
+        ));
+        assert!(matches!(
+            V2MidenExact::price_tag_from_registry(
+                &registry,
+                recipient,
+                &testnet,
+                "USDC",
+                "1.0000001"
+            ),
+            Err(MidenTokenRegistryError::Amount(_))
+        ));
+    }
+
     #[test]
     fn test_price_tag_mainnet() {
-        let recipient: MidenAccountAddress = "0xaabbccddeeff00112233aabbccddee".parse().unwrap();
-        let usdc = MidenTokenDeployment::mainnet_usdc();
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        let faucet: MidenAccountAddress = "0x37d5977a8e16d8205a360820f0230f".parse().unwrap();
+        let usdc = MidenTokenDeployment::mainnet_usdc_with_faucet(faucet);
         let price_tag = V2MidenExact::price_tag(recipient, usdc.amount(500_000));
 
         assert_eq!(price_tag.requirements.network.to_string(), "miden:mainnet");
         assert_eq!(price_tag.requirements.amount, "500000");
     }
 
+    #[test]
+    fn test_try_price_tag_checks_faucet_type() {
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        let usdc = MidenTokenDeployment::testnet_usdc();
+        assert!(V2MidenExact::try_price_tag(recipient.clone(), usdc.amount(1_000_000)).is_ok());
+
+        let wallet_as_asset = MidenTokenDeployment {
+            faucet_id: recipient.clone(),
+            ..usdc
+        };
+        let result = V2MidenExact::try_price_tag(recipient, wallet_as_asset.amount(1_000_000));
+        assert!(matches!(
+            result,
+            Err(x402_chain_miden::v2_miden_exact::MidenExactError::InvalidFaucet(_))
+        ));
+    }
+
+    #[test]
+    fn test_price_tag_extra_token_metadata() {
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
+        let usdc = MidenTokenDeployment::testnet_usdc();
+        let price_tag = V2MidenExact::price_tag(recipient.clone(), usdc.amount(1_000_000));
+        let extra = price_tag.requirements.extra.expect("USDC has metadata");
+        assert_eq!(extra["symbol"], "USDC");
+        assert_eq!(extra["name"], "USD Coin");
+        assert_eq!(extra["decimals"], 6);
+
+        let bare = MidenTokenDeployment::new(
+            MidenChainReference::testnet(),
+            usdc.faucet_id.clone(),
+            usdc.decimals,
+        );
+        let price_tag = V2MidenExact::price_tag(recipient, bare.amount(1_000_000));
+        assert!(price_tag.requirements.extra.is_none());
+    }
+
     #[test]
     fn test_price_tag_different_amounts() {
-        let recipient: MidenAccountAddress = "0xdeadbeef0102030405060708090a0b".parse().unwrap();
+        let recipient: MidenAccountAddress = "0xdeadbeef0102030005060708090a0b".parse().unwrap();
         let usdc = MidenTokenDeployment::testnet_usdc();
 
         // 0.01 USDC
@@ -205,7 +325,7 @@ mod server_tests {
 
     #[test]
     fn test_price_tag_requirements_serializable() {
-        let recipient: MidenAccountAddress = "0xaabbccddeeff00112233aabbccddee".parse().unwrap();
+        let recipient: MidenAccountAddress = "0xaabbccddeeff00102233aabbccddee".parse().unwrap();
         let usdc = MidenTokenDeployment::testnet_usdc();
         let price_tag = V2MidenExact::price_tag(recipient, usdc.amount(1_000_000));
 
@@ -228,10 +348,10 @@ mod facilitator_tests {
 
     #[test]
     fn test_provider_chain_id() {
-        let config = MidenChainConfig {
-            chain_reference: MidenChainReference::testnet(),
-            rpc_url: "https://rpc.testnet.miden.io".to_string(),
-        };
+        let config = MidenChainConfig::new(
+            MidenChainReference::testnet(),
+            "https://rpc.testnet.miden.io".parse().unwrap(),
+        );
         let provider = MidenChainProvider::from_config(&config);
         let chain_id = provider.chain_id();
         assert_eq!(chain_id.to_string(), "miden:testnet");
@@ -239,10 +359,10 @@ mod facilitator_tests {
 
     #[test]
     fn test_provider_mainnet_chain_id() {
-        let config = MidenChainConfig {
-            chain_reference: MidenChainReference::mainnet(),
-            rpc_url: "https://rpc.mainnet.miden.io".to_string(),
-        };
+        let config = MidenChainConfig::new(
+            MidenChainReference::mainnet(),
+            "https://rpc.mainnet.miden.io".parse().unwrap(),
+        );
         let provider = MidenChainProvider::from_config(&config);
         let chain_id = provider.chain_id();
         assert_eq!(chain_id.to_string(), "miden:mainnet");
@@ -273,12 +393,12 @@ mod address_tests {
     fn test_miden_address_from_bytes() {
         // Valid 15-byte address
         let bytes = vec![
-            0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+            0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x03, 0x00, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
             0x0b,
         ];
         let addr = MidenAccountAddress::from_bytes(&bytes).unwrap();
         let hex_str = addr.to_string();
-        assert_eq!(hex_str, "0xdeadbeef0102030405060708090a0b");
+        assert_eq!(hex_str, "0xdeadbeef0102030005060708090a0b");
     }
 
     #[test]
@@ -33,6 +33,120 @@ fn test_account_id_roundtrip() {
     assert_eq!(original, recovered);
 }
 
+/// Test that the fallible, `From` and `TryFrom` conversions agree with
+/// `from_account_id` for every account type and storage mode.
+#[test]
+fn test_account_id_conversions_agree() {
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+
+    for account_type in [
+        AccountType::FungibleFaucet,
+        AccountType::NonFungibleFaucet,
+        AccountType::RegularAccountImmutableCode,
+        AccountType::RegularAccountUpdatableCode,
+    ] {
+        for storage_mode in [
+            AccountStorageMode::Public,
+            AccountStorageMode::Network,
+            AccountStorageMode::Private,
+        ] {
+            let id = AccountId::dummy(
+                [0x3cu8; 15],
+                AccountIdVersion::Version0,
+                account_type,
+                storage_mode,
+            );
+            let addr = MidenAccountAddress::from_account_id(id);
+            assert_eq!(addr.to_hex(), id.to_hex());
+            assert_eq!(MidenAccountAddress::try_from_account_id(id).unwrap(), addr);
+            assert_eq!(MidenAccountAddress::from(id), addr);
+            assert_eq!(AccountId::try_from(&addr).unwrap(), id);
+            assert_eq!(AccountId::try_from(addr).unwrap(), id);
+        }
+    }
+
+    // An address that is not a valid account ID fails to convert instead of panicking.
+    let invalid = MidenAccountAddress::from_bytes_unchecked(&[0xffu8; 15]).unwrap();
+    assert!(AccountId::try_from(&invalid).is_err());
+}
+
+/// Test that `to_felts`/`from_felts` round-trip and agree with
+/// miden-protocol's `[prefix, suffix]` order for every account type.
+#[test]
+fn test_account_felts_roundtrip() {
+    use miden_protocol::Felt;
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+
+    let account_types = [
+        AccountType::FungibleFaucet,
+        AccountType::NonFungibleFaucet,
+        AccountType::RegularAccountImmutableCode,
+        AccountType::RegularAccountUpdatableCode,
+    ];
+    for account_type in account_types {
+        let id = AccountId::dummy(
+            [0xa5u8; 15],
+            AccountIdVersion::Version0,
+            account_type,
+            AccountStorageMode::Public,
+        );
+        let addr = MidenAccountAddress::from_account_id(id);
+
+        let felts = addr.to_felts().expect("valid account ID");
+        assert_eq!(felts, [id.prefix().as_felt(), id.suffix()]);
+        assert_eq!(felts, <[Felt; 2]>::from(id));
+        assert_eq!(MidenAccountAddress::from_felts(felts).unwrap(), addr);
+    }
+
+    // Swapped elements are not a valid account ID.
+    let id = AccountId::dummy(
+        [0xa5u8; 15],
+        AccountIdVersion::Version0,
+        AccountType::RegularAccountUpdatableCode,
+        AccountStorageMode::Public,
+    );
+    assert!(MidenAccountAddress::from_felts([id.suffix(), id.prefix().as_felt()]).is_err());
+}
+
+/// Test that the bit-level account type and storage mode accessors agree with
+/// miden-protocol for every combination.
+#[test]
+fn test_account_type_accessors_match_protocol() {
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+    use x402_chain_miden::chain::{MidenAccountType, MidenStorageMode};
+
+    let account_types = [
+        AccountType::FungibleFaucet,
+        AccountType::NonFungibleFaucet,
+        AccountType::RegularAccountImmutableCode,
+        AccountType::RegularAccountUpdatableCode,
+    ];
+    let storage_modes = [
+        AccountStorageMode::Public,
+        AccountStorageMode::Network,
+        AccountStorageMode::Private,
+    ];
+    for account_type in account_types {
+        for storage_mode in storage_modes {
+            let id = AccountId::dummy(
+                [7u8; 15],
+                AccountIdVersion::Version0,
+                account_type,
+                storage_mode,
+            );
+            let addr = MidenAccountAddress::from_account_id(id);
+
+            assert_eq!(addr.account_type(), MidenAccountType::from(account_type));
+            assert_eq!(
+                addr.storage_mode(),
+                Some(MidenStorageMode::from(storage_mode))
+            );
+            assert_eq!(addr.is_faucet(), account_type.is_faucet());
+            assert_eq!(addr.is_regular_account(), account_type.is_regular_account());
+        }
+    }
+}
+
 /// Test that from_account_id produces a valid hex string.
 #[test]
 fn test_from_account_id_hex_format() {
@@ -156,6 +270,25 @@ fn test_proven_transaction_hex_roundtrip() {
     assert_eq!(tx.id(), recovered.id());
 }
 
+/// Test that converting a TransactionId to MidenTransactionId and back is lossless.
+#[test]
+fn test_transaction_id_roundtrip() {
+    use miden_protocol::Word;
+    use miden_protocol::transaction::TransactionId;
+    use x402_chain_miden::chain::MidenTransactionId;
+
+    let id = TransactionId::new(
+        Word::from([1u32, 2, 3, 4]),
+        Word::from([5u32, 6, 7, 8]),
+        Word::default(),
+        Word::default(),
+    );
+    let converted = MidenTransactionId::from(id);
+
+    assert_eq!(converted.to_hex(), id.to_hex());
+    assert_eq!(converted.to_transaction_id().unwrap(), id);
+}
+
 // ============================================================================
 // TransactionVerifier Tests
 // ============================================================================
@@ -227,9 +360,510 @@ fn test_p2id_note_target_extraction() {
     let inputs = note.recipient().inputs().values();
     assert!(inputs.len() >= 2, "P2ID note should have at least 2 inputs");
 
-    let recovered_target = AccountId::new_unchecked([inputs[1], inputs[0]]);
+    let recovered_target =
+        MidenAccountAddress::from_felts([inputs[1], inputs[0]]).expect("valid target felts");
     assert_eq!(
-        recovered_target, target,
+        recovered_target,
+        MidenAccountAddress::from_account_id(target),
         "Extracted target should match original"
     );
 }
+
+// ============================================================================
+// Faucet Validation Tests
+// ============================================================================
+
+/// Returns the hex ID of a regular (non-faucet) account.
+fn regular_account_hex(seed: u8) -> String {
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+
+    AccountId::dummy(
+        [seed; 15],
+        AccountIdVersion::Version0,
+        AccountType::RegularAccountUpdatableCode,
+        AccountStorageMode::Public,
+    )
+    .to_hex()
+}
+
+/// Test that a payment requirement cannot be created for a regular account asset.
+#[test]
+fn test_create_payment_requirement_rejects_regular_account_asset() {
+    use x402_chain_miden::lightweight::create_payment_requirement;
+    use x402_types::chain::ChainId;
+
+    let result = create_payment_requirement(
+        &regular_account_hex(99),
+        &regular_account_hex(50),
+        1_000_000,
+        ChainId::new("miden", "testnet"),
+    );
+
+    let err = result
+        .err()
+        .expect("regular account asset should be rejected");
+    assert!(err.contains("Invalid faucet"), "unexpected error: {err}");
+}
+
+/// Test that verification fails fast with InvalidFaucet for a regular account asset.
+#[tokio::test]
+async fn test_verify_rejects_regular_account_asset() {
+    use x402_chain_miden::chain::MidenChainReference;
+    use x402_chain_miden::lightweight::{
+        FacilitatorChainState, LightweightPaymentHeader, PaymentContext,
+        verify_lightweight_payment_full,
+    };
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let context = PaymentContext::new(
+        format!("0x{}", "00".repeat(32)),
+        regular_account_hex(50),
+        1_000_000,
+        0,
+        None,
+    );
+    let header = LightweightPaymentHeader {
+        payload_version: x402_chain_miden::lightweight::LIGHTWEIGHT_PAYLOAD_VERSION,
+        note_id: format!("0x{}", "de".repeat(32)),
+        block_num: 10,
+        note_index: 0,
+        note_metadata: "0xaabb".to_string(),
+        inclusion_proof: "0xcafe".to_string(),
+    };
+    let chain_state = FacilitatorChainState::new(
+        "https://rpc.testnet.miden.io".to_string(),
+        MidenChainReference::testnet(),
+    );
+
+    let result = verify_lightweight_payment_full(&context, &header, &chain_state).await;
+    assert!(matches!(result, Err(MidenExactError::InvalidFaucet(_))));
+}
+
+/// Test that the checked price tag rejects a regular account asset.
+#[cfg(feature = "server")]
+#[test]
+fn test_try_price_tag_rejects_regular_account_asset() {
+    use x402_chain_miden::V2MidenExact;
+    use x402_chain_miden::chain::{MidenChainReference, MidenTokenDeployment};
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let token = MidenTokenDeployment::new(
+        MidenChainReference::testnet(),
+        regular_account_hex(50).parse().unwrap(),
+        6,
+    );
+    let pay_to: MidenAccountAddress = regular_account_hex(99).parse().unwrap();
+
+    let result = V2MidenExact::try_price_tag(pay_to.clone(), token.amount(1_000_000));
+    assert!(matches!(result, Err(MidenExactError::InvalidFaucet(_))));
+
+    let faucet = MidenTokenDeployment::new(
+        MidenChainReference::testnet(),
+        "0x37d5977a8e16d8205a360820f0230f".parse().unwrap(),
+        6,
+    );
+    assert!(V2MidenExact::try_price_tag(pay_to, faucet.amount(1_000_000)).is_ok());
+}
+
+// ============================================================================
+// Expiry Tests
+// ============================================================================
+
+/// Test that an expired payment context is rejected with its real expiry time.
+#[tokio::test]
+async fn test_verify_rejects_expired_context() {
+    use x402_chain_miden::chain::MidenChainReference;
+    use x402_chain_miden::lightweight::{
+        FacilitatorChainState, LightweightPaymentHeader, PaymentContext,
+        verify_lightweight_payment_full,
+    };
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let mut context = PaymentContext::new(
+        format!("0x{}", "00".repeat(32)),
+        "0x37d5977a8e16d8205a360820f0230f".to_string(),
+        1_000_000,
+        0,
+        None,
+    );
+    // Fabricate a context issued long ago.
+    context.created_at = 1_000;
+    let header = LightweightPaymentHeader {
+        payload_version: x402_chain_miden::lightweight::LIGHTWEIGHT_PAYLOAD_VERSION,
+        note_id: format!("0x{}", "de".repeat(32)),
+        block_num: 10,
+        note_index: 0,
+        note_metadata: "0xaabb".to_string(),
+        inclusion_proof: "0xcafe".to_string(),
+    };
+    let chain_state = FacilitatorChainState::new(
+        "https://rpc.testnet.miden.io".to_string(),
+        MidenChainReference::testnet(),
+    );
+
+    let err = verify_lightweight_payment_full(&context, &header, &chain_state)
+        .await
+        .unwrap_err();
+    match err {
+        MidenExactError::TransactionExpired {
+            expired_at,
+            current,
+        } => {
+            assert_eq!(expired_at, 1_300);
+            assert!(current > expired_at);
+        }
+        other => panic!("expected TransactionExpired, got {other:?}"),
+    }
+}
+
+/// Test that a structurally invalid header is rejected before anything else,
+/// even for an expired context.
+#[tokio::test]
+async fn test_verify_validates_header_structure_first() {
+    use x402_chain_miden::lightweight::{
+        LightweightPaymentHeader, PaymentContext, verify_lightweight_payment_full,
+    };
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let mut context = PaymentContext::new(
+        format!("0x{}", "00".repeat(32)),
+        "0x37d5977a8e16d8205a360820f0230f".to_string(),
+        1_000_000,
+        0,
+        None,
+    );
+    context.created_at = 1_000;
+    let header = LightweightPaymentHeader {
+        payload_version: x402_chain_miden::lightweight::LIGHTWEIGHT_PAYLOAD_VERSION,
+        note_id: "0xdeadbeef".to_string(),
+        block_num: 10,
+        note_index: 0,
+        note_metadata: "0xaabb".to_string(),
+        inclusion_proof: "0xcafe".to_string(),
+    };
+
+    let err = verify_lightweight_payment_full(&context, &header, &testnet_chain_state())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MidenExactError::MalformedField { .. }));
+    assert_eq!(err.field_error(), Some(("note_id", "wrong_length")));
+}
+
+// ============================================================================
+// Note Tag Tests
+// ============================================================================
+
+/// Builds a payment context and a matching header whose note carries the
+/// tag `tag` returns for the tag the requirement issued.
+///
+/// The header proves the note's inclusion in block 10, whose header is
+/// returned for the caller to cache; without it, verification can only get
+/// as far as the block header lookup.
+fn payment_with_note_tag(
+    tag: impl FnOnce(u32) -> u32,
+) -> (
+    x402_chain_miden::lightweight::PaymentContext,
+    x402_chain_miden::lightweight::LightweightPaymentHeader,
+    x402_chain_miden::lightweight::CachedBlockHeader,
+) {
+    use miden_protocol::Word;
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+    use miden_protocol::asset::{Asset, FungibleAsset};
+    use miden_protocol::block::{BlockNoteIndex, BlockNoteTree};
+    use miden_protocol::note::{Note, NoteAssets, NoteMetadata, NoteTag, NoteType};
+    use miden_protocol::utils::serde::Serializable;
+    use miden_standards::note::utils::build_p2id_recipient;
+    use x402_chain_miden::lightweight::types::parse_serial_num_hex;
+    use x402_chain_miden::lightweight::{
+        CachedBlockHeader, LightweightPaymentHeader, create_payment_requirement,
+    };
+    use x402_types::chain::ChainId;
+
+    let sender = AccountId::dummy(
+        [10u8; 15],
+        AccountIdVersion::Version0,
+        AccountType::RegularAccountUpdatableCode,
+        AccountStorageMode::Public,
+    );
+    let target = AccountId::from_hex(&regular_account_hex(99)).unwrap();
+    let faucet = AccountId::dummy(
+        [50u8; 15],
+        AccountIdVersion::Version0,
+        AccountType::FungibleFaucet,
+        AccountStorageMode::Public,
+    );
+
+    let (requirement, context) = create_payment_requirement(
+        &target.to_hex(),
+        &faucet.to_hex(),
+        1_000_000,
+        ChainId::new("miden", "testnet"),
+    )
+    .expect("requirement should be created");
+
+    let serial_num = parse_serial_num_hex(requirement.serial_num.as_deref().unwrap()).unwrap();
+    let recipient = build_p2id_recipient(target, serial_num).unwrap();
+    let asset = FungibleAsset::new(faucet, requirement.amount).unwrap();
+    let vault = NoteAssets::new(vec![Asset::Fungible(asset)]).unwrap();
+    let metadata = NoteMetadata::new(
+        sender,
+        NoteType::Private,
+        NoteTag::new(tag(requirement.note_tag)),
+    );
+    let note = Note::new(vault, metadata.clone(), recipient);
+
+    let note_index = BlockNoteIndex::new(0, 0).unwrap();
+    let note_tree = BlockNoteTree::with_entries([(note_index, note.id(), &metadata)]).unwrap();
+
+    let header = LightweightPaymentHeader {
+        payload_version: x402_chain_miden::lightweight::LIGHTWEIGHT_PAYLOAD_VERSION,
+        note_id: format!("{}", note.id()),
+        block_num: 10,
+        note_index: note_index.leaf_index_value(),
+        note_metadata: format!("0x{}", hex::encode(metadata.to_bytes())),
+        inclusion_proof: format!("0x{}", hex::encode(note_tree.open(note_index).to_bytes())),
+    };
+    let block = CachedBlockHeader {
+        block_num: 10,
+        note_root: note_tree.root().to_hex(),
+        commitment: Word::default().to_hex(),
+        cached_at: std::time::Instant::now(),
+    };
+    (context, header, block)
+}
+
+fn testnet_chain_state() -> x402_chain_miden::lightweight::FacilitatorChainState {
+    x402_chain_miden::lightweight::FacilitatorChainState::new(
+        "https://rpc.testnet.miden.io".to_string(),
+        x402_chain_miden::chain::MidenChainReference::testnet(),
+    )
+}
+
+/// Test that the issued tag is the one P2ID notes to `pay_to` carry.
+#[test]
+fn test_requirement_note_tag_targets_pay_to() {
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+    use miden_protocol::note::NoteTag;
+    use x402_chain_miden::lightweight::create_payment_requirement;
+    use x402_types::chain::ChainId;
+
+    let faucet = AccountId::dummy(
+        [50u8; 15],
+        AccountIdVersion::Version0,
+        AccountType::FungibleFaucet,
+        AccountStorageMode::Public,
+    );
+    for storage_mode in [AccountStorageMode::Public, AccountStorageMode::Network] {
+        let target = AccountId::dummy(
+            [99u8; 15],
+            AccountIdVersion::Version0,
+            AccountType::RegularAccountUpdatableCode,
+            storage_mode,
+        );
+        let (requirement, context) = create_payment_requirement(
+            &target.to_hex(),
+            &faucet.to_hex(),
+            1_000_000,
+            ChainId::new("miden", "testnet"),
+        )
+        .unwrap();
+
+        let expected = NoteTag::with_account_target(target).as_u32();
+        assert_eq!(requirement.note_tag, expected);
+        assert_eq!(context.note_tag, expected);
+    }
+}
+
+/// Test that a note with the issued tag passes verification.
+#[tokio::test]
+async fn test_verify_accepts_matching_note_tag() {
+    use x402_chain_miden::lightweight::verify_lightweight_payment_full;
+
+    let (context, header, block) = payment_with_note_tag(|issued| issued);
+    let chain_state = testnet_chain_state();
+    chain_state.insert_block_header(block);
+    let response = verify_lightweight_payment_full(&context, &header, &chain_state)
+        .await
+        .unwrap();
+
+    assert!(response.valid);
+    assert_eq!(response.note_id, header.note_id);
+    assert_eq!(response.details.unwrap().note_tag, context.note_tag);
+}
+
+/// Test that an uncached block stops verification after the tag check.
+#[tokio::test]
+async fn test_verify_matching_note_tag_reaches_block_lookup() {
+    use x402_chain_miden::lightweight::verify_lightweight_payment_full;
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let (context, header, _) = payment_with_note_tag(|issued| issued);
+    let result = verify_lightweight_payment_full(&context, &header, &testnet_chain_state()).await;
+
+    assert!(matches!(result, Err(MidenExactError::ProviderError(_))));
+}
+
+/// Test that a note with a corrupted tag is rejected.
+#[tokio::test]
+async fn test_verify_rejects_corrupted_note_tag() {
+    use x402_chain_miden::lightweight::verify_lightweight_payment_full;
+    use x402_chain_miden::v2_miden_exact::MidenExactError;
+
+    let (context, header, block) = payment_with_note_tag(|issued| issued + 1);
+    let chain_state = testnet_chain_state();
+    chain_state.insert_block_header(block);
+    let result = verify_lightweight_payment_full(&context, &header, &chain_state).await;
+
+    assert!(matches!(
+        result,
+        Err(MidenExactError::NoteTagMismatch { expected, got })
+            if expected == context.note_tag && got == context.note_tag + 1
+    ));
+}
+
+/// Test that a corrupted tag only warns when enforcement is disabled.
+#[tokio::test]
+async fn test_verify_note_tag_warn_only() {
+    use x402_chain_miden::lightweight::verify_lightweight_payment_full;
+
+    let (context, header, block) = payment_with_note_tag(|issued| issued + 1);
+    let chain_state = testnet_chain_state().with_note_tag_enforcement(false);
+    chain_state.insert_block_header(block);
+    let response = verify_lightweight_payment_full(&context, &header, &chain_state)
+        .await
+        .unwrap();
+
+    assert!(response.valid);
+    assert_eq!(response.details.unwrap().note_tag, context.note_tag + 1);
+}
+
+/// Test that a fixed serial number reproduces the same note ID across runs.
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_fixed_serial_num_gives_reproducible_note_id() {
+    use miden_protocol::account::{AccountId, AccountIdVersion, AccountStorageMode, AccountType};
+    use miden_protocol::asset::{Asset, FungibleAsset};
+    use miden_protocol::note::{NoteAssets, NoteId};
+    use miden_standards::note::utils::build_p2id_recipient;
+    use x402_chain_miden::lightweight::create_payment_requirement_with_serial_num;
+    use x402_chain_miden::lightweight::types::parse_serial_num_hex;
+    use x402_types::chain::ChainId;
+
+    let target = AccountId::from_hex(&regular_account_hex(99)).unwrap();
+    let faucet = AccountId::dummy(
+        [50u8; 15],
+        AccountIdVersion::Version0,
+        AccountType::FungibleFaucet,
+        AccountStorageMode::Public,
+    );
+
+    let note_id = |serial: [u8; 32]| {
+        let (requirement, _) = create_payment_requirement_with_serial_num(
+            &target.to_hex(),
+            &faucet.to_hex(),
+            1_000_000,
+            ChainId::new("miden", "testnet"),
+            serial,
+        )
+        .unwrap();
+        let serial_num = parse_serial_num_hex(requirement.serial_num.as_deref().unwrap()).unwrap();
+        let recipient = build_p2id_recipient(target, serial_num).unwrap();
+        assert_eq!(recipient.digest().to_hex(), requirement.recipient_digest);
+        let asset = FungibleAsset::new(faucet, requirement.amount).unwrap();
+        let vault = NoteAssets::new(vec![Asset::Fungible(asset)]).unwrap();
+        NoteId::new(recipient.digest(), vault.commitment())
+    };
+
+    assert_eq!(note_id([1u8; 32]), note_id([1u8; 32]));
+    assert_ne!(note_id([1u8; 32]), note_id([2u8; 32]));
+}
+
+// ============================================================================
+// Tracing Tests
+// ============================================================================
+
+/// Test that the verification span records key fields and never the serial number.
+#[tokio::test]
+async fn test_verify_span_records_key_fields() {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use x402_chain_miden::lightweight::verify_lightweight_payment_full;
+
+    type Fields = Arc<Mutex<Vec<(String, String)>>>;
+
+    struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    struct CaptureLayer(Fields);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: Context<'_, S>,
+        ) {
+            if attrs.metadata().name() == "verify_lightweight_payment" {
+                attrs.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+            }
+        }
+
+        fn on_record(
+            &self,
+            _id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: Context<'_, S>,
+        ) {
+            values.record(&mut FieldVisitor(&mut self.0.lock().unwrap()));
+        }
+    }
+
+    let fields: Fields = Arc::default();
+    let subscriber = tracing_subscriber::registry().with(CaptureLayer(fields.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (context, header, _) = payment_with_note_tag(|issued| issued + 1);
+    let result = verify_lightweight_payment_full(&context, &header, &testnet_chain_state()).await;
+    assert!(result.is_err());
+
+    let fields = fields.lock().unwrap();
+    let value_of = |name: &str| {
+        fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_else(|| panic!("field '{name}' not recorded"))
+    };
+
+    assert_eq!(value_of("network"), "testnet");
+    assert_eq!(
+        value_of("faucet"),
+        context
+            .asset_faucet_id
+            .parse::<MidenAccountAddress>()
+            .unwrap()
+            .short()
+    );
+    assert_eq!(value_of("amount"), "1000000");
+    assert_eq!(value_of("note_id"), header.note_id);
+    assert_eq!(value_of("block_num"), "10");
+    assert!(value_of("payload_bytes").parse::<usize>().unwrap() > 0);
+    assert_eq!(value_of("outcome"), "\"rejected\"");
+    assert!(value_of("error").contains("Note tag mismatch"));
+
+    let serial_num = context.serial_num.as_deref().unwrap();
+    assert!(
+        fields
+            .iter()
+            .all(|(_, v)| !v.contains(serial_num) && !v.contains(&context.recipient_digest)),
+        "serial number or recipient digest leaked into the span"
+    );
+}